@@ -0,0 +1,627 @@
+//! Textual assembler/disassembler for `.mdbc` modules
+//!
+//! `disassemble` turns a loaded `BytecodeModule` into a `.mdisasm`
+//! listing; `assemble` parses that listing back into a module that is
+//! byte-for-byte identical to the original modulo formatting (label
+//! names are not preserved, only the PCs they resolve to), except that
+//! the source map is not part of the listing: an assembled module's
+//! source map is always empty. This mirrors
+//! a JVM-style `javap`/`jasmin` pair: the disassembler resolves operands
+//! against `constants`/`function_table`/`global_names` into readable
+//! mnemonics, and the assembler does a two-pass parse (collect label
+//! addresses, then resolve them) to re-emit the binary layout.
+
+use std::collections::HashMap;
+
+use crate::errors::LoadError;
+use crate::instructions::{AssertType, Instruction};
+use crate::loader::{BytecodeModule, SourceMap};
+use crate::values::{ConstantPool, ConstantValue};
+
+/// Render `module` as a `.mdisasm` text listing.
+pub fn disassemble(module: &BytecodeModule) -> String {
+    let mut out = String::new();
+
+    out.push_str(".header\n");
+    out.push_str(&format!("name = {:?}\n", module.name));
+    out.push_str(&format!("version = {}\n", module.version));
+    out.push_str(&format!("flags = {}\n", module.flags));
+    out.push('\n');
+
+    out.push_str(".const\n");
+    for idx in 0..module.constants.len() {
+        if let Some(value) = module.constants.get(idx as u16) {
+            out.push_str(&format!("{}: {}\n", idx, render_constant(value)));
+        }
+    }
+    out.push('\n');
+
+    out.push_str(".globals\n");
+    for (idx, name) in module.global_names.iter().enumerate() {
+        out.push_str(&format!("{}: {:?}\n", idx, name));
+    }
+    out.push('\n');
+
+    let labels = collect_labels(&module.instructions);
+    for (name, start) in function_blocks(module) {
+        let end = function_blocks(module)
+            .into_iter()
+            .map(|(_, s)| s)
+            .filter(|s| *s > start)
+            .min()
+            .unwrap_or(module.instructions.len());
+
+        out.push_str(&format!(".function {} @{}\n", name, start));
+        for pc in start..end {
+            if let Some(label) = labels.get(&pc) {
+                out.push_str(&format!("{}:\n", label));
+            }
+            out.push_str(&format!("    {}\n", render_instruction(&module.instructions[pc], pc, &labels)));
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Parse a `.mdisasm` listing produced by `disassemble` back into a module.
+pub fn assemble(text: &str) -> Result<BytecodeModule, LoadError> {
+    let lines: Vec<&str> = text
+        .lines()
+        .map(|line| {
+            // `//` starts a comment only outside a string literal.
+            let (mut in_string, mut escaped, mut end) = (false, false, line.len());
+            for (i, ch) in line.char_indices() {
+                match ch {
+                    _ if escaped => escaped = false,
+                    '\\' if in_string => escaped = true,
+                    '"' => in_string = !in_string,
+                    '/' if !in_string && line[i..].starts_with("//") => {
+                        end = i;
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+            line[..end].trim()
+        })
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    let mut name = String::from("main");
+    let mut version = 0u32;
+    let mut flags = 0u32;
+    let mut constants = ConstantPool::new();
+    let mut global_names = Vec::new();
+    let mut function_table = HashMap::new();
+
+    // First pass: parse non-instruction sections and collect every
+    // instruction line together with the label that immediately
+    // precedes it, so we know each label's resolved PC before emitting
+    // any instruction that jumps to it.
+    let mut section = Section::Header;
+    let mut inst_lines: Vec<&str> = Vec::new();
+    let mut labels: HashMap<String, usize> = HashMap::new();
+    let mut pending_label: Option<&str> = None;
+
+    for line in &lines {
+        if let Some(rest) = line.strip_prefix(".function ") {
+            section = Section::Function;
+            let (fn_name, offset) = parse_function_header(rest)?;
+            function_table.insert(fn_name, inst_lines.len());
+            let _ = offset;
+            continue;
+        }
+        match *line {
+            ".header" => {
+                section = Section::Header;
+                continue;
+            }
+            ".const" => {
+                section = Section::Const;
+                continue;
+            }
+            ".globals" => {
+                section = Section::Globals;
+                continue;
+            }
+            _ => {}
+        }
+
+        match section {
+            Section::Header => {
+                let (key, value) = split_kv(line)?;
+                match key {
+                    "name" => name = parse_string_literal(value)?,
+                    "version" => version = value.parse().map_err(|_| LoadError::InvalidFormat)?,
+                    "flags" => flags = value.parse().map_err(|_| LoadError::InvalidFormat)?,
+                    _ => return Err(LoadError::InvalidFormat),
+                }
+            }
+            Section::Const => {
+                let (_, value) = split_idx(line)?;
+                constants.push(parse_constant(value)?);
+            }
+            Section::Globals => {
+                let (_, value) = split_idx(line)?;
+                global_names.push(parse_string_literal(value)?);
+            }
+            Section::Function => {
+                if let Some(label) = line.strip_suffix(':') {
+                    pending_label = Some(label);
+                } else {
+                    if let Some(label) = pending_label.take() {
+                        labels.insert(label.to_string(), inst_lines.len());
+                    }
+                    inst_lines.push(line);
+                }
+            }
+        }
+    }
+
+    // Second pass: re-parse the collected instruction lines, resolving
+    // any label operand against the PCs gathered above.
+    let instructions = inst_lines
+        .iter()
+        .enumerate()
+        .map(|(pc, line)| parse_instruction(line, pc, &labels))
+        .collect::<Result<Vec<_>, LoadError>>()?;
+
+    Ok(BytecodeModule {
+        name,
+        version,
+        flags,
+        constants,
+        instructions,
+        function_table,
+        global_names,
+        source_map: SourceMap::new(),
+    })
+}
+
+enum Section {
+    Header,
+    Const,
+    Globals,
+    Function,
+}
+
+/// The set of PCs that are jump targets, each assigned a stable `L<n>`
+/// label in ascending PC order.
+fn collect_labels(instructions: &[Instruction]) -> HashMap<usize, String> {
+    let mut targets: Vec<usize> = instructions
+        .iter()
+        .enumerate()
+        .filter_map(|(pc, inst)| jump_target(pc, inst))
+        .filter(|target| *target < instructions.len())
+        .collect();
+    targets.sort_unstable();
+    targets.dedup();
+
+    targets
+        .into_iter()
+        .enumerate()
+        .map(|(n, pc)| (pc, format!("L{}", n)))
+        .collect()
+}
+
+fn jump_target(pc: usize, inst: &Instruction) -> Option<usize> {
+    match inst {
+        Instruction::JumpR { offset }
+        | Instruction::JumpIfR { offset, .. }
+        | Instruction::JumpIfNotR { offset, .. } => {
+            Some(((pc + 1) as i32 + offset) as usize)
+        }
+        _ => None,
+    }
+}
+
+/// `(function name, start pc)` pairs, sorted by start pc, with an
+/// implicit block named after the module covering pc 0 when it is not
+/// already a named function entry point.
+fn function_blocks(module: &BytecodeModule) -> Vec<(String, usize)> {
+    let mut blocks: Vec<(String, usize)> =
+        module.function_table.iter().map(|(name, pc)| (name.clone(), *pc)).collect();
+    blocks.sort_by_key(|(_, pc)| *pc);
+
+    if blocks.first().map(|(_, pc)| *pc) != Some(0) {
+        blocks.insert(0, (module.name.clone(), 0));
+    }
+
+    blocks
+}
+
+fn render_constant(value: &ConstantValue) -> String {
+    match value {
+        ConstantValue::Int(i) => format!("Int {}", i),
+        ConstantValue::Float(f) => format!("Float {}", f),
+        ConstantValue::String(s) => format!("String {:?}", s),
+        ConstantValue::URL(u) => format!("URL {:?}", u),
+        ConstantValue::Function(name) => format!("Function {:?}", name),
+    }
+}
+
+fn parse_constant(text: &str) -> Result<ConstantValue, LoadError> {
+    let (tag, rest) = text.split_once(' ').ok_or(LoadError::InvalidFormat)?;
+    let rest = rest.trim();
+    Ok(match tag {
+        "Int" => ConstantValue::Int(rest.parse().map_err(|_| LoadError::InvalidFormat)?),
+        "Float" => ConstantValue::Float(rest.parse().map_err(|_| LoadError::InvalidFormat)?),
+        "String" => ConstantValue::String(parse_string_literal(rest)?),
+        "URL" => ConstantValue::URL(parse_string_literal(rest)?),
+        "Function" => ConstantValue::Function(parse_string_literal(rest)?),
+        _ => return Err(LoadError::InvalidFormat),
+    })
+}
+
+fn render_instruction(inst: &Instruction, pc: usize, labels: &HashMap<usize, String>) -> String {
+    let label_for = |target: Option<usize>| -> String {
+        target
+            .and_then(|t| labels.get(&t))
+            .cloned()
+            .unwrap_or_else(|| "?".to_string())
+    };
+
+    match inst {
+        Instruction::LoadConstR { dst, const_idx } => format!("LoadConstR r{}, #{}", dst, const_idx),
+        Instruction::MoveR { dst, src } => format!("MoveR r{}, r{}", dst, src),
+        Instruction::LoadGlobalR { dst, name_idx } => format!("LoadGlobalR r{}, @{}", dst, name_idx),
+        Instruction::StoreGlobalR { src, name_idx } => format!("StoreGlobalR r{}, @{}", src, name_idx),
+        Instruction::DefineR { dst, type_id } => format!("DefineR r{}, {}", dst, type_id),
+        Instruction::CheckTypeR { dst, src, type_id } => {
+            format!("CheckTypeR r{}, r{}, {}", dst, src, type_id)
+        }
+        Instruction::CastR { dst, src, to_type } => format!("CastR r{}, r{}, {}", dst, src, to_type),
+        Instruction::AddR { dst, left, right } => format!("AddR r{}, r{}, r{}", dst, left, right),
+        Instruction::SubR { dst, left, right } => format!("SubR r{}, r{}, r{}", dst, left, right),
+        Instruction::MulR { dst, left, right } => format!("MulR r{}, r{}, r{}", dst, left, right),
+        Instruction::DivR { dst, left, right } => format!("DivR r{}, r{}, r{}", dst, left, right),
+        Instruction::ModR { dst, left, right } => format!("ModR r{}, r{}, r{}", dst, left, right),
+        Instruction::NegR { dst, src } => format!("NegR r{}, r{}", dst, src),
+        Instruction::NotR { dst, src } => format!("NotR r{}, r{}", dst, src),
+        Instruction::AndR { dst, left, right } => format!("AndR r{}, r{}, r{}", dst, left, right),
+        Instruction::OrR { dst, left, right } => format!("OrR r{}, r{}, r{}", dst, left, right),
+        Instruction::EqR { dst, left, right } => format!("EqR r{}, r{}, r{}", dst, left, right),
+        Instruction::NeqR { dst, left, right } => format!("NeqR r{}, r{}, r{}", dst, left, right),
+        Instruction::LtR { dst, left, right } => format!("LtR r{}, r{}, r{}", dst, left, right),
+        Instruction::GtR { dst, left, right } => format!("GtR r{}, r{}, r{}", dst, left, right),
+        Instruction::LteR { dst, left, right } => format!("LteR r{}, r{}, r{}", dst, left, right),
+        Instruction::GteR { dst, left, right } => format!("GteR r{}, r{}, r{}", dst, left, right),
+        Instruction::JumpR { offset } => {
+            format!("JumpR {}", label_for(jump_target(pc, &Instruction::JumpR { offset: *offset })))
+        }
+        Instruction::JumpIfR { cond, offset } => format!(
+            "JumpIfR r{}, {}",
+            cond,
+            label_for(jump_target(pc, &Instruction::JumpIfR { cond: *cond, offset: *offset }))
+        ),
+        Instruction::JumpIfNotR { cond, offset } => format!(
+            "JumpIfNotR r{}, {}",
+            cond,
+            label_for(jump_target(pc, &Instruction::JumpIfNotR { cond: *cond, offset: *offset }))
+        ),
+        Instruction::CallR { func, args, dst } => {
+            let args = args.iter().map(|r| format!("r{}", r)).collect::<Vec<_>>().join(", ");
+            format!("CallR r{}, r{}, [{}]", dst, func, args)
+        }
+        Instruction::ReturnR { src } => match src {
+            Some(src) => format!("ReturnR r{}", src),
+            None => "ReturnR".to_string(),
+        },
+        Instruction::PhiR { dst, sources } => {
+            let sources = sources
+                .iter()
+                .map(|(reg, block)| format!("r{}:{}", reg, block))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("PhiR r{}, [{}]", dst, sources)
+        }
+        Instruction::AssertR { reg, assert_type, msg_idx } => {
+            format!("AssertR r{}, {}, #{}", reg, render_assert_type(assert_type), msg_idx)
+        }
+        Instruction::ScopeEnterR { scope_id } => format!("ScopeEnterR {}", scope_id),
+        Instruction::ScopeExitR { scope_id } => format!("ScopeExitR {}", scope_id),
+        Instruction::ConcatStrR { dst, left, right } => {
+            format!("ConcatStrR r{}, r{}, r{}", dst, left, right)
+        }
+        Instruction::StrLenR { dst, str_reg } => format!("StrLenR r{}, r{}", dst, str_reg),
+        Instruction::NewArrayR { dst, size } => format!("NewArrayR r{}, r{}", dst, size),
+        Instruction::ArrayGetR { dst, array, index } => {
+            format!("ArrayGetR r{}, r{}, r{}", dst, array, index)
+        }
+        Instruction::ArraySetR { array, index, value } => {
+            format!("ArraySetR r{}, r{}, r{}", array, index, value)
+        }
+        Instruction::ArrayLenR { dst, array } => format!("ArrayLenR r{}, r{}", dst, array),
+        Instruction::DebugPrint { src } => format!("DebugPrint r{}", src),
+        Instruction::BreakPoint => "BreakPoint".to_string(),
+        Instruction::Halt => "Halt".to_string(),
+        Instruction::Nop => "Nop".to_string(),
+    }
+}
+
+fn render_assert_type(assert_type: &AssertType) -> String {
+    match assert_type {
+        AssertType::True => "True".to_string(),
+        AssertType::NonNull => "NonNull".to_string(),
+        AssertType::Range { min, max } => format!("Range({}, {})", min, max),
+    }
+}
+
+fn parse_instruction(
+    line: &str,
+    pc: usize,
+    labels: &HashMap<String, usize>,
+) -> Result<Instruction, LoadError> {
+    let (mnemonic, rest) = line.split_once(' ').unwrap_or((line, ""));
+    let operands = split_operands(rest);
+    let op = |i: usize| -> Result<&str, LoadError> {
+        operands.get(i).copied().ok_or(LoadError::InvalidFormat)
+    };
+    let reg = |i: usize| -> Result<u8, LoadError> { parse_reg(op(i)?) };
+    let offset_to = |label: &str| -> Result<i32, LoadError> {
+        let target = *labels.get(label).ok_or(LoadError::InvalidFormat)?;
+        Ok(target as i32 - (pc + 1) as i32)
+    };
+
+    Ok(match mnemonic {
+        "LoadConstR" => {
+            Instruction::LoadConstR { dst: reg(0)?, const_idx: parse_tagged(op(1)?, '#')? }
+        }
+        "MoveR" => Instruction::MoveR { dst: reg(0)?, src: reg(1)? },
+        "LoadGlobalR" => {
+            Instruction::LoadGlobalR { dst: reg(0)?, name_idx: parse_tagged(op(1)?, '@')? }
+        }
+        "StoreGlobalR" => {
+            Instruction::StoreGlobalR { src: reg(0)?, name_idx: parse_tagged(op(1)?, '@')? }
+        }
+        "DefineR" => Instruction::DefineR {
+            dst: reg(0)?,
+            type_id: op(1)?.parse().map_err(|_| LoadError::InvalidFormat)?,
+        },
+        "CheckTypeR" => Instruction::CheckTypeR {
+            dst: reg(0)?,
+            src: reg(1)?,
+            type_id: op(2)?.parse().map_err(|_| LoadError::InvalidFormat)?,
+        },
+        "CastR" => Instruction::CastR {
+            dst: reg(0)?,
+            src: reg(1)?,
+            to_type: op(2)?.parse().map_err(|_| LoadError::InvalidFormat)?,
+        },
+        "AddR" => Instruction::AddR { dst: reg(0)?, left: reg(1)?, right: reg(2)? },
+        "SubR" => Instruction::SubR { dst: reg(0)?, left: reg(1)?, right: reg(2)? },
+        "MulR" => Instruction::MulR { dst: reg(0)?, left: reg(1)?, right: reg(2)? },
+        "DivR" => Instruction::DivR { dst: reg(0)?, left: reg(1)?, right: reg(2)? },
+        "ModR" => Instruction::ModR { dst: reg(0)?, left: reg(1)?, right: reg(2)? },
+        "NegR" => Instruction::NegR { dst: reg(0)?, src: reg(1)? },
+        "NotR" => Instruction::NotR { dst: reg(0)?, src: reg(1)? },
+        "AndR" => Instruction::AndR { dst: reg(0)?, left: reg(1)?, right: reg(2)? },
+        "OrR" => Instruction::OrR { dst: reg(0)?, left: reg(1)?, right: reg(2)? },
+        "EqR" => Instruction::EqR { dst: reg(0)?, left: reg(1)?, right: reg(2)? },
+        "NeqR" => Instruction::NeqR { dst: reg(0)?, left: reg(1)?, right: reg(2)? },
+        "LtR" => Instruction::LtR { dst: reg(0)?, left: reg(1)?, right: reg(2)? },
+        "GtR" => Instruction::GtR { dst: reg(0)?, left: reg(1)?, right: reg(2)? },
+        "LteR" => Instruction::LteR { dst: reg(0)?, left: reg(1)?, right: reg(2)? },
+        "GteR" => Instruction::GteR { dst: reg(0)?, left: reg(1)?, right: reg(2)? },
+        "JumpR" => Instruction::JumpR { offset: offset_to(op(0)?)? },
+        "JumpIfR" => Instruction::JumpIfR { cond: reg(0)?, offset: offset_to(op(1)?)? },
+        "JumpIfNotR" => Instruction::JumpIfNotR { cond: reg(0)?, offset: offset_to(op(1)?)? },
+        "CallR" => {
+            let dst = reg(0)?;
+            let func = reg(1)?;
+            let args_text = op(2)?.trim_start_matches('[').trim_end_matches(']');
+            let args = if args_text.trim().is_empty() {
+                Vec::new()
+            } else {
+                args_text
+                    .split(',')
+                    .map(|a| parse_reg(a.trim()))
+                    .collect::<Result<Vec<_>, _>>()?
+            };
+            Instruction::CallR { func, args, dst }
+        }
+        "ReturnR" => Instruction::ReturnR { src: operands.first().map(|r| parse_reg(r)).transpose()? },
+        "PhiR" => {
+            let dst = reg(0)?;
+            let sources_text = op(1)?.trim_start_matches('[').trim_end_matches(']');
+            let sources = if sources_text.trim().is_empty() {
+                Vec::new()
+            } else {
+                sources_text
+                    .split(',')
+                    .map(|entry| {
+                        let (reg_part, block_part) =
+                            entry.trim().split_once(':').ok_or(LoadError::InvalidFormat)?;
+                        Ok((
+                            parse_reg(reg_part)?,
+                            block_part.parse().map_err(|_| LoadError::InvalidFormat)?,
+                        ))
+                    })
+                    .collect::<Result<Vec<_>, LoadError>>()?
+            };
+            Instruction::PhiR { dst, sources }
+        }
+        "AssertR" => Instruction::AssertR {
+            reg: reg(0)?,
+            assert_type: parse_assert_type(op(1)?)?,
+            msg_idx: parse_tagged(op(2)?, '#')?,
+        },
+        "ScopeEnterR" => Instruction::ScopeEnterR {
+            scope_id: op(0)?.parse().map_err(|_| LoadError::InvalidFormat)?,
+        },
+        "ScopeExitR" => Instruction::ScopeExitR {
+            scope_id: op(0)?.parse().map_err(|_| LoadError::InvalidFormat)?,
+        },
+        "ConcatStrR" => Instruction::ConcatStrR { dst: reg(0)?, left: reg(1)?, right: reg(2)? },
+        "StrLenR" => Instruction::StrLenR { dst: reg(0)?, str_reg: reg(1)? },
+        "NewArrayR" => Instruction::NewArrayR { dst: reg(0)?, size: reg(1)? },
+        "ArrayGetR" => Instruction::ArrayGetR { dst: reg(0)?, array: reg(1)?, index: reg(2)? },
+        "ArraySetR" => Instruction::ArraySetR { array: reg(0)?, index: reg(1)?, value: reg(2)? },
+        "ArrayLenR" => Instruction::ArrayLenR { dst: reg(0)?, array: reg(1)? },
+        "DebugPrint" => Instruction::DebugPrint { src: reg(0)? },
+        "BreakPoint" => Instruction::BreakPoint,
+        "Halt" => Instruction::Halt,
+        "Nop" => Instruction::Nop,
+        _ => return Err(LoadError::InvalidFormat),
+    })
+}
+
+fn parse_assert_type(text: &str) -> Result<AssertType, LoadError> {
+    if text == "True" {
+        return Ok(AssertType::True);
+    }
+    if text == "NonNull" {
+        return Ok(AssertType::NonNull);
+    }
+    if let Some(inner) = text.strip_prefix("Range(").and_then(|s| s.strip_suffix(')')) {
+        let (min, max) = inner.split_once(',').ok_or(LoadError::InvalidFormat)?;
+        return Ok(AssertType::Range {
+            min: min.trim().parse().map_err(|_| LoadError::InvalidFormat)?,
+            max: max.trim().parse().map_err(|_| LoadError::InvalidFormat)?,
+        });
+    }
+    Err(LoadError::InvalidFormat)
+}
+
+fn parse_reg(text: &str) -> Result<u8, LoadError> {
+    text.strip_prefix('r')
+        .and_then(|n| n.parse().ok())
+        .ok_or(LoadError::InvalidFormat)
+}
+
+fn parse_tagged(text: &str, tag: char) -> Result<u16, LoadError> {
+    text.strip_prefix(tag)
+        .and_then(|n| n.parse().ok())
+        .ok_or(LoadError::InvalidFormat)
+}
+
+fn parse_function_header(rest: &str) -> Result<(String, usize), LoadError> {
+    let (name, offset) = rest.trim().split_once(" @").ok_or(LoadError::InvalidFormat)?;
+    Ok((name.trim().to_string(), offset.trim().parse().map_err(|_| LoadError::InvalidFormat)?))
+}
+
+fn split_kv(line: &str) -> Result<(&str, &str), LoadError> {
+    let (key, value) = line.split_once('=').ok_or(LoadError::InvalidFormat)?;
+    Ok((key.trim(), value.trim()))
+}
+
+fn split_idx(line: &str) -> Result<(&str, &str), LoadError> {
+    let (idx, value) = line.split_once(':').ok_or(LoadError::InvalidFormat)?;
+    Ok((idx.trim(), value.trim()))
+}
+
+fn parse_string_literal(text: &str) -> Result<String, LoadError> {
+    let inner = text.strip_prefix('"').and_then(|s| s.strip_suffix('"')).ok_or(LoadError::InvalidFormat)?;
+    unescape_debug(inner)
+}
+
+/// Undo the escaping `{:?}` applies to a `&str` (quotes, backslashes,
+/// `\n`/`\r`/`\t`, and `\u{...}` for other control characters), so
+/// `render_constant`'s `{:?}`-rendered strings round-trip exactly.
+fn unescape_debug(text: &str) -> Result<String, LoadError> {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            out.push(ch);
+            continue;
+        }
+
+        match chars.next().ok_or(LoadError::InvalidFormat)? {
+            '"' => out.push('"'),
+            '\'' => out.push('\''),
+            '\\' => out.push('\\'),
+            'n' => out.push('\n'),
+            'r' => out.push('\r'),
+            't' => out.push('\t'),
+            '0' => out.push('\0'),
+            'u' => {
+                if chars.next() != Some('{') {
+                    return Err(LoadError::InvalidFormat);
+                }
+                let hex: String = chars.by_ref().take_while(|c| *c != '}').collect();
+                let code = u32::from_str_radix(&hex, 16).map_err(|_| LoadError::InvalidFormat)?;
+                out.push(char::from_u32(code).ok_or(LoadError::InvalidFormat)?);
+            }
+            _ => return Err(LoadError::InvalidFormat),
+        }
+    }
+
+    Ok(out)
+}
+
+/// Split an operand list on top-level commas, treating `[...]` groups
+/// (call args, phi sources) as a single operand.
+fn split_operands(text: &str) -> Vec<&str> {
+    let mut operands = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+
+    for (i, ch) in text.char_indices() {
+        match ch {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            ',' if depth == 0 => {
+                operands.push(text[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if start < text.len() {
+        operands.push(text[start..].trim());
+    }
+    operands.into_iter().filter(|s| !s.is_empty()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A module whose name, constants, and global names all contain
+    /// characters `{:?}` escapes (quotes, backslashes, tabs, newlines),
+    /// so a disassemble/assemble round trip exercises `unescape_debug`.
+    fn sample_module() -> BytecodeModule {
+        let mut constants = ConstantPool::new();
+        constants.push(ConstantValue::String("say \"hi\"\tthen\\go".to_string()));
+        constants.push(ConstantValue::URL("https://example.com/\"q\"".to_string()));
+        constants.push(ConstantValue::Function("odd\\name\nhere".to_string()));
+        constants.push(ConstantValue::Int(42));
+        constants.push(ConstantValue::Float(1.5));
+
+        BytecodeModule {
+            name: "weird \"module\" name".to_string(),
+            version: 3,
+            flags: 7,
+            constants,
+            instructions: Vec::new(),
+            function_table: HashMap::new(),
+            global_names: vec!["g\"1".to_string(), "plain".to_string()],
+            source_map: SourceMap::new(),
+        }
+    }
+
+    #[test]
+    fn assemble_disassemble_round_trips_escaped_strings() {
+        let module = sample_module();
+        let reassembled = assemble(&disassemble(&module)).unwrap();
+
+        assert_eq!(reassembled.name, module.name);
+        assert_eq!(reassembled.version, module.version);
+        assert_eq!(reassembled.flags, module.flags);
+        assert_eq!(reassembled.global_names, module.global_names);
+        assert_eq!(reassembled.constants.len(), module.constants.len());
+        for idx in 0..module.constants.len() as u16 {
+            let original = module.constants.get(idx).map(render_constant);
+            let round_tripped = reassembled.constants.get(idx).map(render_constant);
+            assert_eq!(round_tripped, original);
+        }
+    }
+
+    #[test]
+    fn parse_string_literal_rejects_unknown_escapes() {
+        assert!(parse_string_literal(r#""bad \q escape""#).is_err());
+    }
+}
@@ -3,12 +3,35 @@
 //! This module provides the Python interface to the Rust VM.
 
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use pyo3::prelude::*;
-use pyo3::exceptions::PyRuntimeError;
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
 
 use crate::vm::VM;
 use crate::loader::BytecodeLoader;
+use crate::values::{FunctionRef, Value};
+
+/// A callable handle to a function in a loaded module, returned in
+/// place of a `Value::Function` so it can be round-tripped back into
+/// `RustVM::call` instead of degrading to a name string.
+#[pyclass]
+#[derive(Clone)]
+pub struct VmFunction {
+    name: String,
+}
+
+#[pymethods]
+impl VmFunction {
+    #[getter]
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<function {}>", self.name)
+    }
+}
 
 /// Rust VM exposed to Python
 #[pyclass]
@@ -42,12 +65,13 @@ impl RustVM {
     pub fn execute(&mut self) -> PyResult<Option<PyObject>> {
         match self.vm.run() {
             Ok(Some(value)) => {
-                Python::with_gil(|py| {
-                    Ok(Some(self.value_to_python(py, &value)))
-                })
+                Python::with_gil(|py| self.value_to_python(py, &value).map(Some))
             }
             Ok(None) => Ok(None),
-            Err(e) => Err(PyRuntimeError::new_err(format!("Runtime error: {}", e))),
+            Err(e) => Err(PyRuntimeError::new_err(format!(
+                "Runtime error: {}",
+                self.vm.describe_error(&e)
+            ))),
         }
     }
 
@@ -60,23 +84,87 @@ impl RustVM {
     pub fn instruction_count(&self) -> usize {
         self.vm.instruction_count
     }
+
+    /// Disassemble the loaded module into a `.mdisasm` text listing
+    pub fn disassemble(&self) -> PyResult<String> {
+        let module = self.vm.module.as_ref().ok_or_else(|| {
+            PyRuntimeError::new_err("No module loaded")
+        })?;
+        Ok(crate::assembler::disassemble(module))
+    }
+
+    /// Call a function in the loaded module by name, passing `args`
+    /// converted from Python, and convert the result back
+    pub fn call(&mut self, py: Python<'_>, name: String, args: Vec<PyObject>) -> PyResult<Option<PyObject>> {
+        let args = args
+            .into_iter()
+            .map(|arg| python_to_value(arg.bind(py)))
+            .collect::<PyResult<Vec<_>>>()?;
+
+        match self.vm.call_function(&name, args) {
+            Ok(Some(value)) => self.value_to_python(py, &value).map(Some),
+            Ok(None) => Ok(None),
+            Err(e) => Err(PyRuntimeError::new_err(format!(
+                "Runtime error: {}",
+                self.vm.describe_error(&e)
+            ))),
+        }
+    }
+
+    /// List the names of every function in the loaded module's function table
+    pub fn list_functions(&self) -> Vec<String> {
+        self.vm
+            .module
+            .as_ref()
+            .map(|module| module.function_table.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Convert a Python object to a VM `Value`.
+///
+/// `None` maps to `Empty`, `bool` to `Bool`, `int` to `Int`, `float` to
+/// `Float`, `str` to `String`, and a `VmFunction` handle round-trips
+/// back to the `Function` it was produced from.
+fn python_to_value(obj: &Bound<'_, PyAny>) -> PyResult<Value> {
+    if obj.is_none() {
+        return Ok(Value::Empty);
+    }
+    if let Ok(handle) = obj.extract::<VmFunction>() {
+        return Ok(Value::Function(FunctionRef { name: handle.name }));
+    }
+    if let Ok(b) = obj.extract::<bool>() {
+        return Ok(Value::Bool(b));
+    }
+    if let Ok(i) = obj.extract::<i64>() {
+        return Ok(Value::Int(i));
+    }
+    if let Ok(f) = obj.extract::<f64>() {
+        return Ok(Value::Float(f));
+    }
+    if let Ok(s) = obj.extract::<String>() {
+        return Ok(Value::String(Arc::new(s)));
+    }
+
+    Err(PyValueError::new_err(format!(
+        "Unsupported Python type for VM value conversion: {}",
+        obj.get_type()
+    )))
 }
 
 // Helper methods implementation (not exposed to Python)
 impl RustVM {
     /// Convert a Rust value to Python
-    fn value_to_python(&self, py: Python<'_>, value: &crate::values::Value) -> PyObject {
-        use crate::values::Value;
-
-        match value {
+    fn value_to_python(&self, py: Python<'_>, value: &Value) -> PyResult<PyObject> {
+        Ok(match value {
             Value::Empty => py.None(),
             Value::Bool(b) => b.into_py(py),
             Value::Int(i) => i.into_py(py),
             Value::Float(f) => f.into_py(py),
             Value::String(s) => s.as_ref().into_py(py),
             Value::URL(u) => u.as_ref().into_py(py),
-            Value::Function(f) => format!("function<{}>", f.name).into_py(py),
-        }
+            Value::Function(f) => Py::new(py, VmFunction { name: f.name.clone() })?.into_py(py),
+        })
     }
 }
 
@@ -84,6 +172,7 @@ impl RustVM {
 #[pymodule]
 fn machine_dialect_vm(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<RustVM>()?;
+    m.add_class::<VmFunction>()?;
     m.add("__version__", crate::VM_VERSION)?;
     Ok(())
 }
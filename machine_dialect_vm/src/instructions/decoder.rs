@@ -1,18 +1,211 @@
 //! Instruction decoder
 //!
-//! This module decodes bytecode into instructions.
+//! This module decodes the instruction-stream section of a `.mdbc`
+//! file into `Instruction`s. See `loader::bytecode` for the overall
+//! file layout; this module owns only the `[opcode][operands...]`
+//! encoding of the instruction stream itself.
 
-use crate::instructions::Instruction;
-use crate::errors::{RuntimeError, Result};
+use crate::errors::LoadError;
+use crate::instructions::{AssertType, Instruction};
 
-/// Instruction decoder
+/// A bounds-checked little-endian cursor over a byte slice, shared by
+/// the instruction decoder and the bytecode section parsers.
+pub(crate) struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    pub(crate) fn eof(&self) -> bool {
+        self.pos >= self.data.len()
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], LoadError> {
+        let end = self.pos.checked_add(len).ok_or(LoadError::InvalidFormat)?;
+        let slice = self.data.get(self.pos..end).ok_or(LoadError::InvalidFormat)?;
+        self.pos = end;
+        Ok(slice)
+    }
+
+    pub(crate) fn u8(&mut self) -> Result<u8, LoadError> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub(crate) fn u16(&mut self) -> Result<u16, LoadError> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    pub(crate) fn u32(&mut self) -> Result<u32, LoadError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    pub(crate) fn i64(&mut self) -> Result<i64, LoadError> {
+        Ok(i64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    pub(crate) fn f64(&mut self) -> Result<f64, LoadError> {
+        Ok(f64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    /// A `u32` length prefix followed by that many UTF-8 bytes.
+    pub(crate) fn string(&mut self) -> Result<String, LoadError> {
+        let len = self.u32()? as usize;
+        String::from_utf8(self.take(len)?.to_vec()).map_err(|_| LoadError::InvalidFormat)
+    }
+
+    /// A register or local slot, encoded as `u16` but narrowed to the
+    /// `u8` register file index the VM actually uses.
+    pub(crate) fn reg(&mut self) -> Result<u8, LoadError> {
+        Ok(self.u16()? as u8)
+    }
+}
+
+/// Decodes the instruction-stream section of a `.mdbc` module.
 pub struct InstructionDecoder;
 
 impl InstructionDecoder {
-    /// Decode bytecode into instructions
-    pub fn decode(bytecode: &[u8]) -> Result<Vec<Instruction>> {
-        // TODO: Implement bytecode decoding
-        // For now, return empty vector
-        Ok(Vec::new())
+    /// Decode `bytecode` (the instruction-stream section only) into a
+    /// `Vec<Instruction>`, the shared routine `BytecodeLoader::parse_bytecode`
+    /// calls for that section.
+    ///
+    /// `constant_count` is the number of entries in the module's constant
+    /// pool, used to validate constant-pool indices; jump targets are
+    /// validated against the decoded instruction count itself once the
+    /// whole stream has been read.
+    pub fn decode(bytecode: &[u8], constant_count: usize) -> Result<Vec<Instruction>, LoadError> {
+        let mut reader = Reader::new(bytecode);
+        let mut instructions = Vec::new();
+
+        while !reader.eof() {
+            instructions.push(Self::decode_one(&mut reader)?);
+        }
+
+        for inst in &instructions {
+            if let Some(const_idx) = constant_index(inst) {
+                if const_idx as usize >= constant_count {
+                    return Err(LoadError::InvalidFormat);
+                }
+            }
+        }
+
+        for (pc, inst) in instructions.iter().enumerate() {
+            if let Some(offset) = jump_offset(inst) {
+                let target = (pc + 1) as i64 + offset as i64;
+                if target < 0 || target as usize > instructions.len() {
+                    return Err(LoadError::InvalidFormat);
+                }
+            }
+        }
+
+        Ok(instructions)
+    }
+
+    fn decode_one(reader: &mut Reader) -> Result<Instruction, LoadError> {
+        let opcode = reader.u8()?;
+
+        Ok(match opcode {
+            0x00 => Instruction::LoadConstR { dst: reader.reg()?, const_idx: reader.u16()? },
+            0x01 => Instruction::MoveR { dst: reader.reg()?, src: reader.reg()? },
+            0x02 => Instruction::LoadGlobalR { dst: reader.reg()?, name_idx: reader.u16()? },
+            0x03 => Instruction::StoreGlobalR { src: reader.reg()?, name_idx: reader.u16()? },
+            0x04 => Instruction::DefineR { dst: reader.reg()?, type_id: reader.u16()? },
+            0x05 => Instruction::CheckTypeR {
+                dst: reader.reg()?,
+                src: reader.reg()?,
+                type_id: reader.u16()?,
+            },
+            0x06 => Instruction::CastR {
+                dst: reader.reg()?,
+                src: reader.reg()?,
+                to_type: reader.u16()?,
+            },
+            0x07 => Instruction::AddR { dst: reader.reg()?, left: reader.reg()?, right: reader.reg()? },
+            0x08 => Instruction::SubR { dst: reader.reg()?, left: reader.reg()?, right: reader.reg()? },
+            0x09 => Instruction::MulR { dst: reader.reg()?, left: reader.reg()?, right: reader.reg()? },
+            0x0A => Instruction::DivR { dst: reader.reg()?, left: reader.reg()?, right: reader.reg()? },
+            0x0B => Instruction::ModR { dst: reader.reg()?, left: reader.reg()?, right: reader.reg()? },
+            0x0C => Instruction::NegR { dst: reader.reg()?, src: reader.reg()? },
+            0x0D => Instruction::NotR { dst: reader.reg()?, src: reader.reg()? },
+            0x0E => Instruction::AndR { dst: reader.reg()?, left: reader.reg()?, right: reader.reg()? },
+            0x0F => Instruction::OrR { dst: reader.reg()?, left: reader.reg()?, right: reader.reg()? },
+            0x10 => Instruction::EqR { dst: reader.reg()?, left: reader.reg()?, right: reader.reg()? },
+            0x11 => Instruction::NeqR { dst: reader.reg()?, left: reader.reg()?, right: reader.reg()? },
+            0x12 => Instruction::LtR { dst: reader.reg()?, left: reader.reg()?, right: reader.reg()? },
+            0x13 => Instruction::GtR { dst: reader.reg()?, left: reader.reg()?, right: reader.reg()? },
+            0x14 => Instruction::LteR { dst: reader.reg()?, left: reader.reg()?, right: reader.reg()? },
+            0x15 => Instruction::GteR { dst: reader.reg()?, left: reader.reg()?, right: reader.reg()? },
+            0x16 => Instruction::JumpR { offset: reader.u32()? as i32 },
+            0x17 => Instruction::JumpIfR { cond: reader.reg()?, offset: reader.u32()? as i32 },
+            0x18 => Instruction::JumpIfNotR { cond: reader.reg()?, offset: reader.u32()? as i32 },
+            0x19 => {
+                let func = reader.reg()?;
+                let dst = reader.reg()?;
+                let argc = reader.u16()?;
+                let args = (0..argc).map(|_| reader.reg()).collect::<Result<Vec<_>, _>>()?;
+                Instruction::CallR { func, args, dst }
+            }
+            0x1A => {
+                let has_src = reader.u8()?;
+                let src = if has_src != 0 { Some(reader.reg()?) } else { None };
+                Instruction::ReturnR { src }
+            }
+            0x1B => {
+                let dst = reader.reg()?;
+                let count = reader.u16()?;
+                let sources = (0..count)
+                    .map(|_| Ok((reader.reg()?, reader.u16()?)))
+                    .collect::<Result<Vec<_>, LoadError>>()?;
+                Instruction::PhiR { dst, sources }
+            }
+            0x1C => {
+                let reg = reader.reg()?;
+                let assert_type = match reader.u8()? {
+                    0 => AssertType::True,
+                    1 => AssertType::NonNull,
+                    2 => AssertType::Range { min: reader.i64()?, max: reader.i64()? },
+                    _ => return Err(LoadError::InvalidFormat),
+                };
+                let msg_idx = reader.u16()?;
+                Instruction::AssertR { reg, assert_type, msg_idx }
+            }
+            0x1D => Instruction::ScopeEnterR { scope_id: reader.u16()? },
+            0x1E => Instruction::ScopeExitR { scope_id: reader.u16()? },
+            0x1F => Instruction::ConcatStrR { dst: reader.reg()?, left: reader.reg()?, right: reader.reg()? },
+            0x20 => Instruction::StrLenR { dst: reader.reg()?, str_reg: reader.reg()? },
+            0x21 => Instruction::NewArrayR { dst: reader.reg()?, size: reader.reg()? },
+            0x22 => Instruction::ArrayGetR { dst: reader.reg()?, array: reader.reg()?, index: reader.reg()? },
+            0x23 => Instruction::ArraySetR { array: reader.reg()?, index: reader.reg()?, value: reader.reg()? },
+            0x24 => Instruction::ArrayLenR { dst: reader.reg()?, array: reader.reg()? },
+            0x25 => Instruction::DebugPrint { src: reader.reg()? },
+            0x26 => Instruction::BreakPoint,
+            0x27 => Instruction::Halt,
+            0x28 => Instruction::Nop,
+            _ => return Err(LoadError::InvalidFormat),
+        })
+    }
+}
+
+/// The constant-pool index an instruction references, if any.
+fn constant_index(inst: &Instruction) -> Option<u16> {
+    match inst {
+        Instruction::LoadConstR { const_idx, .. } => Some(*const_idx),
+        Instruction::LoadGlobalR { name_idx, .. } => Some(*name_idx),
+        Instruction::StoreGlobalR { name_idx, .. } => Some(*name_idx),
+        Instruction::AssertR { msg_idx, .. } => Some(*msg_idx),
+        _ => None,
+    }
+}
+
+/// The relative jump offset an instruction branches by, if any.
+fn jump_offset(inst: &Instruction) -> Option<i32> {
+    match inst {
+        Instruction::JumpR { offset }
+        | Instruction::JumpIfR { offset, .. }
+        | Instruction::JumpIfNotR { offset, .. } => Some(*offset),
+        _ => None,
     }
 }
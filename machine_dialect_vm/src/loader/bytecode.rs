@@ -6,11 +6,58 @@ use std::path::Path;
 use std::fs;
 use std::collections::HashMap;
 
-use crate::values::ConstantPool;
-use crate::instructions::Instruction;
-use crate::errors::LoadError;
+use crate::values::{ConstantPool, ConstantValue};
+use crate::instructions::{Instruction, InstructionDecoder};
+use crate::instructions::decoder::Reader;
+use crate::errors::{LoadError, SourceLocation};
 use super::metadata::MetadataFile;
 
+/// Size of the fixed `.mdbc` header: magic (4) + version (4) + flags (4)
+/// + five little-endian `u32` section offsets (constant pool,
+/// instruction stream, function table, global-name table, source map),
+/// each relative to the start of the file.
+const HEADER_LEN: usize = 4 + 4 + 4 + 5 * 4;
+
+const MAGIC: &[u8; 4] = b"MDBC";
+
+/// Maps instruction PC ranges to the source location that produced
+/// them, parallel to a module's constant pool.
+#[derive(Clone, Debug, Default)]
+pub struct SourceMap {
+    /// `(start_pc, location)` entries, kept sorted by `start_pc`. A PC
+    /// resolves to the last entry whose `start_pc` is `<=` it, i.e. the
+    /// location holds for every instruction up to the next entry.
+    entries: Vec<(usize, SourceLocation)>,
+}
+
+impl SourceMap {
+    /// An empty source map; every PC resolves to `None`.
+    pub fn new() -> Self {
+        Self { entries: Vec::new() }
+    }
+
+    /// Record that instructions from `start_pc` onward map to `location`.
+    pub fn insert(&mut self, start_pc: usize, location: SourceLocation) {
+        self.entries.push((start_pc, location));
+        self.entries.sort_by_key(|(pc, _)| *pc);
+    }
+
+    /// Resolve `pc` to the source location that produced it, if known.
+    pub fn resolve(&self, pc: usize) -> Option<SourceLocation> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|(start_pc, _)| *start_pc <= pc)
+            .map(|(_, location)| location.clone())
+    }
+
+    /// The `(start_pc, location)` entries in `start_pc` order, for
+    /// callers that need to serialize the whole map.
+    pub fn entries(&self) -> &[(usize, SourceLocation)] {
+        &self.entries
+    }
+}
+
 /// Bytecode module
 #[derive(Clone, Debug)]
 pub struct BytecodeModule {
@@ -28,6 +75,8 @@ pub struct BytecodeModule {
     pub function_table: HashMap<String, usize>,
     /// Global names
     pub global_names: Vec<String>,
+    /// Maps instructions back to the source locations that produced them
+    pub source_map: SourceMap,
 }
 
 /// Bytecode loader
@@ -57,25 +106,236 @@ impl BytecodeLoader {
 
     /// Parse bytecode data
     fn parse_bytecode(data: &[u8]) -> std::result::Result<BytecodeModule, LoadError> {
-        if data.len() < 24 {
+        if data.len() < HEADER_LEN {
             return Err(LoadError::InvalidFormat);
         }
 
         // Check magic number
-        if &data[0..4] != b"MDBC" {
+        if &data[0..4] != MAGIC {
             return Err(LoadError::InvalidMagic);
         }
 
-        // TODO: Implement full bytecode parsing
-        // For now, return a dummy module
+        let mut header = Reader::new(&data[4..HEADER_LEN]);
+        let version = header.u32()?;
+        let flags = header.u32()?;
+        let const_pool_offset = header.u32()? as usize;
+        let instructions_offset = header.u32()? as usize;
+        let function_table_offset = header.u32()? as usize;
+        let global_names_offset = header.u32()? as usize;
+        let source_map_offset = header.u32()? as usize;
+
+        let constants = Self::parse_constant_pool(data, const_pool_offset, instructions_offset)?;
+
+        let instructions_section = data
+            .get(instructions_offset..function_table_offset)
+            .ok_or(LoadError::InvalidFormat)?;
+        let instructions = InstructionDecoder::decode(instructions_section, constants.len())?;
+
+        let function_table =
+            Self::parse_function_table(data, function_table_offset, global_names_offset)?;
+        let global_names =
+            Self::parse_global_names(data, global_names_offset, source_map_offset)?;
+        let source_map = Self::parse_source_map(data, source_map_offset)?;
+
         Ok(BytecodeModule {
             name: "main".to_string(),
-            version: 1,
-            flags: 0,
-            constants: ConstantPool::new(),
-            instructions: Vec::new(),
-            function_table: HashMap::new(),
-            global_names: Vec::new(),
+            version,
+            flags,
+            constants,
+            instructions,
+            function_table,
+            global_names,
+            source_map,
         })
     }
+
+    /// Parse the `[count: u32][tag: u8, payload...]*` constant pool section.
+    fn parse_constant_pool(
+        data: &[u8],
+        start: usize,
+        end: usize,
+    ) -> std::result::Result<ConstantPool, LoadError> {
+        let section = data.get(start..end).ok_or(LoadError::InvalidFormat)?;
+        let mut reader = Reader::new(section);
+        let count = reader.u32()?;
+
+        let mut constants = ConstantPool::new();
+        for _ in 0..count {
+            let value = match reader.u8()? {
+                0 => ConstantValue::Int(reader.i64()?),
+                1 => ConstantValue::Float(reader.f64()?),
+                2 => ConstantValue::String(reader.string()?),
+                3 => ConstantValue::URL(reader.string()?),
+                4 => ConstantValue::Function(reader.string()?),
+                _ => return Err(LoadError::InvalidFormat),
+            };
+            constants.push(value);
+        }
+
+        Ok(constants)
+    }
+
+    /// Parse the `[count: u32][name: string, offset: u32]*` function table section.
+    fn parse_function_table(
+        data: &[u8],
+        start: usize,
+        end: usize,
+    ) -> std::result::Result<HashMap<String, usize>, LoadError> {
+        let section = data.get(start..end).ok_or(LoadError::InvalidFormat)?;
+        let mut reader = Reader::new(section);
+        let count = reader.u32()?;
+
+        let mut function_table = HashMap::new();
+        for _ in 0..count {
+            let name = reader.string()?;
+            let offset = reader.u32()? as usize;
+            function_table.insert(name, offset);
+        }
+
+        Ok(function_table)
+    }
+
+    /// Parse the `[count: u32][name: string]*` global-name table section.
+    fn parse_global_names(
+        data: &[u8],
+        start: usize,
+        end: usize,
+    ) -> std::result::Result<Vec<String>, LoadError> {
+        let section = data.get(start..end).ok_or(LoadError::InvalidFormat)?;
+        let mut reader = Reader::new(section);
+        let count = reader.u32()?;
+
+        let mut global_names = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            global_names.push(reader.string()?);
+        }
+
+        Ok(global_names)
+    }
+
+    /// Parse the trailing `[count: u32][start_pc: u32, file: string,
+    /// line: u32, column: u32]*` source-map section.
+    fn parse_source_map(data: &[u8], start: usize) -> std::result::Result<SourceMap, LoadError> {
+        let section = data.get(start..).ok_or(LoadError::InvalidFormat)?;
+        let mut reader = Reader::new(section);
+        let count = reader.u32()?;
+
+        let mut source_map = SourceMap::new();
+        for _ in 0..count {
+            let start_pc = reader.u32()? as usize;
+            let file = reader.string()?;
+            let line = reader.u32()?;
+            let column = reader.u32()?;
+            source_map.insert(start_pc, SourceLocation { file, line, column });
+        }
+
+        Ok(source_map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push_str(buf: &mut Vec<u8>, s: &str) {
+        buf.extend_from_slice(&(s.len() as u32).to_le_bytes());
+        buf.extend_from_slice(s.as_bytes());
+    }
+
+    /// Builds a minimal well-formed `.mdbc` file: no constants, no
+    /// instructions, no functions, one global, and one source-map entry.
+    fn sample_module_bytes() -> Vec<u8> {
+        let mut body = Vec::new();
+        body.extend_from_slice(MAGIC);
+        body.extend_from_slice(&7u32.to_le_bytes()); // version
+        body.extend_from_slice(&0u32.to_le_bytes()); // flags
+
+        // Section offsets are back-patched once each section's start is known.
+        let offsets_pos = body.len();
+        body.extend_from_slice(&[0u8; 5 * 4]);
+
+        let const_pool_offset = body.len();
+        body.extend_from_slice(&0u32.to_le_bytes()); // 0 constants
+
+        let instructions_offset = body.len();
+        // empty instruction stream
+
+        let function_table_offset = body.len();
+        body.extend_from_slice(&0u32.to_le_bytes()); // 0 functions
+
+        let global_names_offset = body.len();
+        body.extend_from_slice(&1u32.to_le_bytes()); // 1 global
+        push_str(&mut body, "counter");
+
+        let source_map_offset = body.len();
+        body.extend_from_slice(&1u32.to_le_bytes()); // 1 entry
+        body.extend_from_slice(&0u32.to_le_bytes()); // start_pc
+        push_str(&mut body, "test.md");
+        body.extend_from_slice(&3u32.to_le_bytes()); // line
+        body.extend_from_slice(&5u32.to_le_bytes()); // column
+
+        let mut offsets = Vec::new();
+        for offset in [
+            const_pool_offset,
+            instructions_offset,
+            function_table_offset,
+            global_names_offset,
+            source_map_offset,
+        ] {
+            offsets.extend_from_slice(&(offset as u32).to_le_bytes());
+        }
+        body[offsets_pos..offsets_pos + offsets.len()].copy_from_slice(&offsets);
+
+        body
+    }
+
+    #[test]
+    fn parse_bytecode_round_trips_every_section() {
+        let module = BytecodeLoader::parse_bytecode(&sample_module_bytes()).unwrap();
+
+        assert_eq!(module.version, 7);
+        assert_eq!(module.constants.len(), 0);
+        assert!(module.instructions.is_empty());
+        assert!(module.function_table.is_empty());
+        assert_eq!(module.global_names, vec!["counter".to_string()]);
+
+        let location = module.source_map.resolve(0).unwrap();
+        assert_eq!(location.file, "test.md");
+        assert_eq!(location.line, 3);
+        assert_eq!(location.column, 5);
+    }
+
+    #[test]
+    fn parse_bytecode_rejects_truncated_data() {
+        let data = sample_module_bytes();
+
+        // Too short for even the fixed header.
+        assert!(matches!(
+            BytecodeLoader::parse_bytecode(&data[..HEADER_LEN - 1]),
+            Err(LoadError::InvalidFormat)
+        ));
+
+        // Header is intact but every section past it is missing.
+        assert!(matches!(
+            BytecodeLoader::parse_bytecode(&data[..HEADER_LEN]),
+            Err(LoadError::InvalidFormat)
+        ));
+
+        // Truncated mid-way through the source map's last entry.
+        assert!(matches!(
+            BytecodeLoader::parse_bytecode(&data[..data.len() - 2]),
+            Err(LoadError::InvalidFormat)
+        ));
+    }
+
+    #[test]
+    fn parse_bytecode_rejects_bad_magic() {
+        let mut data = sample_module_bytes();
+        data[0] = b'X';
+
+        assert!(matches!(
+            BytecodeLoader::parse_bytecode(&data),
+            Err(LoadError::InvalidMagic)
+        ));
+    }
 }
@@ -2,6 +2,7 @@
 //!
 //! This module implements the main VM execution engine.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::values::{Value, Type, ConstantPool};
@@ -9,7 +10,454 @@ use crate::vm::{RegisterFile, VMState};
 use crate::instructions::{Instruction, AssertType};
 use crate::runtime::{ArithmeticOps, LogicOps, StringOps};
 use crate::errors::{RuntimeError, Result, StackFrame};
-use crate::loader::{BytecodeModule, MetadataFile};
+use crate::loader::{BytecodeModule, MetadataFile, SourceMap};
+
+/// Number of times a loop header must be reached before its body is
+/// recorded as a trace.
+const HOT_LOOP_THRESHOLD: u32 = 50;
+
+/// Maximum number of instructions a single trace may record; a
+/// recording that reaches it is finalized at that length.
+const MAX_TRACE_LENGTH: usize = 1000;
+
+/// A condition that must still hold for a recorded trace to keep
+/// replaying linearly. If a guard fails, execution deoptimizes back to
+/// `execute_instruction` at the instruction the guard covers.
+#[derive(Debug, Clone)]
+enum Guard {
+    /// A conditional branch's condition register must still evaluate to
+    /// the same truthiness it had while recording.
+    Branch { cond: u8, truthy: bool },
+    /// A register must still hold a value of this type, so a redundant
+    /// `CastR` observed during recording can be skipped on replay.
+    Type { reg: u8, expected: Type },
+}
+
+impl Guard {
+    /// Check whether this guard still holds against the live VM state.
+    fn holds(&self, vm: &VM) -> bool {
+        match self {
+            Guard::Branch { cond, truthy } => vm.registers.get(*cond).is_truthy() == *truthy,
+            Guard::Type { reg, expected } => vm.registers.get_type(*reg) == expected,
+        }
+    }
+}
+
+/// A single recorded trace step: the instruction executed, plus any
+/// guard that must hold for the trace to remain valid on replay.
+#[derive(Debug, Clone)]
+struct TraceStep {
+    pc: usize,
+    instruction: Instruction,
+    guard: Option<Guard>,
+}
+
+/// A linear, guard-checked recording of a hot loop's body.
+#[derive(Debug, Clone)]
+struct Trace {
+    /// PC of the loop header this trace starts (and cycles back) at.
+    header_pc: usize,
+    steps: Vec<TraceStep>,
+}
+
+/// Accumulates instructions and guards while a hot loop is being
+/// recorded, until control flows back to the loop header (a complete
+/// cyclic trace) or `MAX_TRACE_LENGTH` is hit (the trace is finalized
+/// at that length).
+struct TraceRecorder {
+    header_pc: usize,
+    steps: Vec<TraceStep>,
+}
+
+impl TraceRecorder {
+    fn new(header_pc: usize) -> Self {
+        Self {
+            header_pc,
+            steps: Vec::new(),
+        }
+    }
+
+    fn record(&mut self, pc: usize, instruction: Instruction, guard: Option<Guard>) {
+        self.steps.push(TraceStep { pc, instruction, guard });
+    }
+
+    fn is_too_long(&self) -> bool {
+        self.steps.len() >= MAX_TRACE_LENGTH
+    }
+
+    fn finalize(self) -> Trace {
+        Trace {
+            header_pc: self.header_pc,
+            steps: self.steps,
+        }
+    }
+}
+
+/// How many more instructions should run before the debugger stops
+/// again, set by the action chosen at the last stop.
+#[derive(Debug, Clone, Copy)]
+enum StepMode {
+    /// Stop before the very next instruction.
+    Instruction,
+    /// Stop once `call_stack` is back to this depth or shallower, i.e.
+    /// a call made by the stepped-over instruction has returned.
+    Over { depth: usize },
+    /// Stop once `call_stack` is shallower than this depth, i.e. the
+    /// current frame has returned.
+    Out { depth: usize },
+}
+
+/// An action chosen by a `Debugger` in response to a stop.
+pub enum DebugAction {
+    /// Resume normal execution.
+    Continue,
+    /// Execute exactly one more instruction, then stop again.
+    StepInstruction,
+    /// Run until the current call (if any) returns, then stop.
+    StepOver,
+    /// Run until the enclosing call returns, then stop.
+    StepOut,
+    /// Add a breakpoint at `pc`.
+    SetBreakpoint(usize),
+    /// Remove the breakpoint at `pc`.
+    ClearBreakpoint(usize),
+    /// Print the value of a named global.
+    ReadLocal(String),
+    /// Set the value of a named global.
+    WriteLocal(String, Value),
+    /// Detach the debugger and run to completion.
+    Quit,
+}
+
+/// Embedder hook that drives single-stepping and inspection when the
+/// VM stops at a breakpoint.
+///
+/// `next_action` is called once per user interaction while stopped.
+/// `Continue`, `StepInstruction`, `StepOver`, `StepOut`, and `Quit` end
+/// the stop; every other action is applied immediately and the
+/// debugger is asked again.
+pub trait Debugger {
+    fn next_action(&mut self, pc: usize, stack_trace: &[StackFrame]) -> DebugAction;
+}
+
+/// Default line-based REPL `Debugger` that reads commands from stdin,
+/// for embedders (and a future CLI) that don't supply their own.
+pub struct LineDebugger;
+
+impl Debugger for LineDebugger {
+    fn next_action(&mut self, pc: usize, stack_trace: &[StackFrame]) -> DebugAction {
+        use std::io::Write;
+
+        println!("Stopped at PC {}", pc);
+        for frame in stack_trace {
+            println!("  in {} (pc {})", frame.function, frame.pc);
+        }
+
+        loop {
+            print!("(mdb) ");
+            let _ = std::io::stdout().flush();
+
+            let mut line = String::new();
+            if std::io::stdin().read_line(&mut line).unwrap_or(0) == 0 {
+                return DebugAction::Continue;
+            }
+
+            let mut parts = line.trim().split_whitespace();
+            match parts.next() {
+                Some("c") | Some("continue") => return DebugAction::Continue,
+                Some("s") | Some("step") => return DebugAction::StepInstruction,
+                Some("o") | Some("over") => return DebugAction::StepOver,
+                Some("out") => return DebugAction::StepOut,
+                Some("q") | Some("quit") => return DebugAction::Quit,
+                Some("break") => match parts.next().and_then(|arg| arg.parse().ok()) {
+                    Some(pc) => return DebugAction::SetBreakpoint(pc),
+                    None => println!("usage: break <pc>"),
+                },
+                Some("clear") => match parts.next().and_then(|arg| arg.parse().ok()) {
+                    Some(pc) => return DebugAction::ClearBreakpoint(pc),
+                    None => println!("usage: clear <pc>"),
+                },
+                Some("print") => match parts.next() {
+                    Some(name) => return DebugAction::ReadLocal(name.to_string()),
+                    None => println!("usage: print <name>"),
+                },
+                Some("set") => match (parts.next(), parts.next()) {
+                    (Some(name), Some(value)) => {
+                        return DebugAction::WriteLocal(name.to_string(), parse_debug_value(value));
+                    }
+                    _ => println!("usage: set <name> <value>"),
+                },
+                _ => println!(
+                    "commands: continue, step, over, out, break <pc>, clear <pc>, print <name>, set <name> <value>, quit"
+                ),
+            }
+        }
+    }
+}
+
+/// Parse a value typed at the `set` debugger command.
+fn parse_debug_value(raw: &str) -> Value {
+    if let Ok(i) = raw.parse::<i64>() {
+        Value::Int(i)
+    } else if let Ok(f) = raw.parse::<f64>() {
+        Value::Float(f)
+    } else if let Ok(b) = raw.parse::<bool>() {
+        Value::Bool(b)
+    } else {
+        Value::String(Arc::new(raw.to_string()))
+    }
+}
+
+/// Optimization level for `VM::with_optimizations`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum OptLevel {
+    /// Run instructions exactly as loaded (the default).
+    None,
+    /// Constant folding, redundant-cast elimination, and local
+    /// (single-block) dead-code elimination.
+    Basic,
+}
+
+/// The destination register an instruction writes, if it is pure (has
+/// no effect besides that write, and cannot itself raise a
+/// `RuntimeError`) and so is a candidate for local dead code
+/// elimination. `LoadGlobalR`, `CastR`, `NewArrayR`, and `ArrayGetR` all
+/// write a register but can fail (`InvalidConstant`, a bad cast,
+/// negative size, out-of-bounds index); eliminating them as dead would
+/// silently discard the error they'd raise unoptimized.
+fn pure_dst(inst: &Instruction) -> Option<u8> {
+    match inst {
+        Instruction::LoadConstR { dst, .. }
+        | Instruction::MoveR { dst, .. }
+        | Instruction::DefineR { dst, .. }
+        | Instruction::CheckTypeR { dst, .. }
+        | Instruction::AddR { dst, .. }
+        | Instruction::SubR { dst, .. }
+        | Instruction::MulR { dst, .. }
+        | Instruction::DivR { dst, .. }
+        | Instruction::ModR { dst, .. }
+        | Instruction::NegR { dst, .. }
+        | Instruction::NotR { dst, .. }
+        | Instruction::AndR { dst, .. }
+        | Instruction::OrR { dst, .. }
+        | Instruction::EqR { dst, .. }
+        | Instruction::NeqR { dst, .. }
+        | Instruction::LtR { dst, .. }
+        | Instruction::GtR { dst, .. }
+        | Instruction::LteR { dst, .. }
+        | Instruction::GteR { dst, .. }
+        | Instruction::ConcatStrR { dst, .. }
+        | Instruction::StrLenR { dst, .. }
+        | Instruction::ArrayLenR { dst, .. } => Some(*dst),
+        _ => None,
+    }
+}
+
+/// Every register an instruction writes, a superset of `pure_dst` that
+/// also covers writes `pure_dst` excludes from DCE eligibility because
+/// they can fail or have side effects (`LoadGlobalR`, `CastR`,
+/// `NewArrayR`, `ArrayGetR`), plus the writes `pure_dst` never
+/// considered at all (`CallR`'s `dst`, `PhiR`'s `dst`, `ArraySetR`'s
+/// in-place rewrite of its `array` operand). Used only to invalidate
+/// `known_types`, never for dead-code elimination.
+fn written_registers(inst: &Instruction) -> Vec<u8> {
+    if let Some(dst) = pure_dst(inst) {
+        return vec![dst];
+    }
+
+    match inst {
+        Instruction::LoadGlobalR { dst, .. }
+        | Instruction::CastR { dst, .. }
+        | Instruction::NewArrayR { dst, .. }
+        | Instruction::ArrayGetR { dst, .. }
+        | Instruction::CallR { dst, .. }
+        | Instruction::PhiR { dst, .. } => vec![*dst],
+        Instruction::ArraySetR { array, .. } => vec![*array],
+        _ => Vec::new(),
+    }
+}
+
+/// Every register an instruction reads.
+fn sources(inst: &Instruction) -> Vec<u8> {
+    match inst {
+        Instruction::MoveR { src, .. }
+        | Instruction::CastR { src, .. }
+        | Instruction::NegR { src, .. }
+        | Instruction::NotR { src, .. }
+        | Instruction::CheckTypeR { src, .. } => vec![*src],
+        Instruction::StrLenR { str_reg, .. } => vec![*str_reg],
+        Instruction::StoreGlobalR { src, .. } => vec![*src],
+        Instruction::AddR { left, right, .. }
+        | Instruction::SubR { left, right, .. }
+        | Instruction::MulR { left, right, .. }
+        | Instruction::DivR { left, right, .. }
+        | Instruction::ModR { left, right, .. }
+        | Instruction::AndR { left, right, .. }
+        | Instruction::OrR { left, right, .. }
+        | Instruction::EqR { left, right, .. }
+        | Instruction::NeqR { left, right, .. }
+        | Instruction::LtR { left, right, .. }
+        | Instruction::GtR { left, right, .. }
+        | Instruction::LteR { left, right, .. }
+        | Instruction::GteR { left, right, .. }
+        | Instruction::ConcatStrR { left, right, .. } => vec![*left, *right],
+        Instruction::NewArrayR { size, .. } => vec![*size],
+        Instruction::ArrayGetR { array, index, .. } => vec![*array, *index],
+        Instruction::ArraySetR { array, index, value } => vec![*array, *index, *value],
+        Instruction::ArrayLenR { array, .. } => vec![*array],
+        Instruction::JumpIfR { cond, .. } | Instruction::JumpIfNotR { cond, .. } => vec![*cond],
+        Instruction::AssertR { reg, .. } => vec![*reg],
+        Instruction::DebugPrint { src } => vec![*src],
+        Instruction::ReturnR { src } => src.iter().copied().collect(),
+        Instruction::PhiR { sources, .. } => sources.iter().map(|(reg, _)| *reg).collect(),
+        Instruction::CallR { func, args, .. } => {
+            let mut regs = vec![*func];
+            regs.extend(args.iter().copied());
+            regs
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Resolve the absolute PC a branch instruction targets, using the same
+/// arithmetic `execute_instruction` uses at runtime (relative to the PC
+/// immediately after the branch itself).
+fn branch_target(pc: usize, inst: &Instruction) -> Option<usize> {
+    match inst {
+        Instruction::JumpR { offset }
+        | Instruction::JumpIfR { offset, .. }
+        | Instruction::JumpIfNotR { offset, .. } => Some(((pc + 1) as i32 + offset) as usize),
+        _ => None,
+    }
+}
+
+/// Leader PCs (basic block starts): PC 0, every branch target, and
+/// every instruction immediately after a branch or return.
+fn basic_block_leaders(instructions: &[Instruction]) -> Vec<usize> {
+    let mut leaders = vec![0usize];
+
+    for (pc, inst) in instructions.iter().enumerate() {
+        if let Some(target) = branch_target(pc, inst) {
+            if target < instructions.len() {
+                leaders.push(target);
+            }
+        }
+        if matches!(
+            inst,
+            Instruction::JumpR { .. }
+                | Instruction::JumpIfR { .. }
+                | Instruction::JumpIfNotR { .. }
+                | Instruction::ReturnR { .. }
+        ) && pc + 1 < instructions.len()
+        {
+            leaders.push(pc + 1);
+        }
+    }
+
+    leaders.sort_unstable();
+    leaders.dedup();
+    leaders
+}
+
+/// Run constant-folding, redundant-cast elimination, and local dead
+/// code elimination over one basic block, replacing eliminated
+/// instructions with `Instruction::Nop` so every other PC and jump
+/// offset in the module stays valid.
+fn optimize_block(instructions: &mut [Instruction], constants: &ConstantPool) {
+    use std::collections::HashMap;
+
+    // Registers known, at this point in the block, to already hold a
+    // value of a given type -- lets a later `CastR` to that same type
+    // be folded away as a no-op move.
+    let mut known_types: HashMap<u8, Type> = HashMap::new();
+    // PC of the last pure instruction that wrote each register and has
+    // not been read since, i.e. a candidate for dead-code elimination
+    // if nothing reads it before the block ends.
+    let mut last_writer: HashMap<u8, usize> = HashMap::new();
+
+    for pc in 0..instructions.len() {
+        for src in sources(&instructions[pc]) {
+            // The register is read, so its last writer is live.
+            last_writer.remove(&src);
+        }
+
+        // Taken before the cast fold below can turn a `CastR` into a `MoveR`.
+        let sets_type =
+            matches!(instructions[pc], Instruction::CastR { .. } | Instruction::LoadConstR { .. });
+
+        if let Instruction::LoadConstR { dst, const_idx } = instructions[pc] {
+            if let Some(value) = constants.get(const_idx) {
+                known_types.insert(dst, value.to_value().type_of());
+            }
+        }
+
+        if let Instruction::CastR { dst, src, to_type } = instructions[pc] {
+            let target_type = match to_type {
+                0 => Type::Empty,
+                1 => Type::Bool,
+                2 => Type::Int,
+                3 => Type::Float,
+                4 => Type::String,
+                5 => Type::Function,
+                6 => Type::URL,
+                _ => Type::Unknown,
+            };
+
+            // Constant-fold a cast whose source is a constant already
+            // of the target type: it is just a move. Casts to any other
+            // type raise in `cast_value`, so they are left in place.
+            if known_types.get(&src) == Some(&target_type)
+                && matches!(target_type, Type::Bool | Type::Int | Type::Float | Type::String)
+            {
+                instructions[pc] = Instruction::MoveR { dst, src };
+            }
+            // Either way `dst` now holds `target_type`, folded or not.
+            known_types.insert(dst, target_type);
+        }
+
+        // Any register this instruction writes no longer holds the type
+        // `known_types` last recorded for it, whether or not the write
+        // is DCE-eligible: a stale entry surviving a `CallR` result, a
+        // `PhiR` merge, or an `ArraySetR` rewrite would let a later
+        // `CastR` fold into a no-op move against the wrong type.
+        // `LoadConstR`/`CastR` are excluded because they already insert
+        // the correct fresh type for `dst` above.
+        for dst in written_registers(&instructions[pc]) {
+            if !sets_type {
+                known_types.remove(&dst);
+            }
+        }
+
+        if let Some(dst) = pure_dst(&instructions[pc]) {
+            if let Some(prev_pc) = last_writer.insert(dst, pc) {
+                // `prev_pc` wrote `dst` and nothing read it before this
+                // instruction overwrote it: dead.
+                instructions[prev_pc] = Instruction::Nop;
+            }
+        }
+    }
+}
+
+/// Run the optimization pipeline over a flat instruction stream and
+/// return an optimized stream the existing `execute_instruction` loop
+/// can run unchanged: the block CFG and jump offsets are preserved,
+/// only dead/redundant instructions are replaced with `Nop`.
+pub fn optimize(instructions: &[Instruction], constants: &ConstantPool, level: OptLevel) -> Vec<Instruction> {
+    if level == OptLevel::None {
+        return instructions.to_vec();
+    }
+
+    let mut optimized = instructions.to_vec();
+    let mut leaders = basic_block_leaders(&optimized);
+    leaders.push(optimized.len());
+
+    for window in leaders.windows(2) {
+        let (start, end) = (window[0], window[1]);
+        optimize_block(&mut optimized[start..end], constants);
+    }
+
+    optimized
+}
 
 /// Virtual Machine
 pub struct VM {
@@ -25,10 +473,26 @@ pub struct VM {
     pub constants: ConstantPool,
     /// Metadata
     pub metadata: Option<MetadataFile>,
+    /// Maps PCs back to the source locations that produced them
+    source_map: SourceMap,
     /// Debug mode
     pub debug_mode: bool,
     /// Instruction count (for profiling)
     pub instruction_count: usize,
+    /// Execution counts for each loop-header PC, used to detect hot loops
+    hot_loop_counts: HashMap<usize, u32>,
+    /// Finalized traces, keyed by loop-header PC
+    traces: HashMap<usize, Trace>,
+    /// In-progress recording, if a loop header just crossed the hot threshold
+    recording: Option<TraceRecorder>,
+    /// PCs that should always stop execution when `debug_mode` is on
+    breakpoints: std::collections::HashSet<usize>,
+    /// Embedder-supplied debugger; `None` means breakpoints are inert
+    debugger: Option<Box<dyn Debugger>>,
+    /// Pending single-step request from the last debugger stop
+    step_mode: Option<StepMode>,
+    /// Optimization level applied to modules as they are loaded
+    opt_level: OptLevel,
 }
 
 impl VM {
@@ -41,19 +505,50 @@ impl VM {
             instructions: Vec::new(),
             constants: ConstantPool::new(),
             metadata: None,
+            source_map: SourceMap::new(),
             debug_mode: false,
             instruction_count: 0,
+            hot_loop_counts: HashMap::new(),
+            traces: HashMap::new(),
+            recording: None,
+            breakpoints: std::collections::HashSet::new(),
+            debugger: None,
+            step_mode: None,
+            opt_level: OptLevel::None,
         }
     }
 
+    /// Create a VM that runs each loaded module through the
+    /// optimization pipeline first, trading startup cost for faster
+    /// steady-state execution. The naive path (`VM::new`) stays
+    /// available for debugging, where instruction-for-instruction
+    /// fidelity to the source module matters.
+    pub fn with_optimizations(level: OptLevel) -> Self {
+        Self {
+            opt_level: level,
+            ..Self::new()
+        }
+    }
+
+    /// Attach a debugger. While attached, `debug_mode` stops execution
+    /// at breakpoints and hands control to it.
+    pub fn set_debugger(&mut self, debugger: Box<dyn Debugger>) {
+        self.debugger = Some(debugger);
+    }
+
     /// Load a module and metadata
     pub fn load_module(&mut self, module: BytecodeModule, metadata: Option<MetadataFile>) -> Result<()> {
-        self.instructions = module.instructions.clone();
+        self.instructions = optimize(&module.instructions, &module.constants, self.opt_level);
         self.constants = module.constants.clone();
+        self.source_map = module.source_map.clone();
         self.module = Some(module);
         self.metadata = metadata;
         self.state.reset();
         self.registers.clear();
+        self.hot_loop_counts.clear();
+        self.traces.clear();
+        self.recording = None;
+        self.step_mode = None;
         Ok(())
     }
 
@@ -75,6 +570,42 @@ impl VM {
         Ok(last_value)
     }
 
+    /// Call a function from the loaded module's `function_table` by
+    /// name, passing `args` in the argument registers (`r0`..`r15`)
+    /// `CallR` itself uses, and run it to completion. Resets register
+    /// and control state first, so this is meant for invoking a
+    /// function directly (e.g. from an embedder) rather than from the
+    /// middle of a running program.
+    pub fn call_function(&mut self, name: &str, args: Vec<Value>) -> Result<Option<Value>> {
+        let func_offset = *self
+            .module
+            .as_ref()
+            .ok_or(RuntimeError::ModuleNotLoaded)?
+            .function_table
+            .get(name)
+            .ok_or_else(|| RuntimeError::UndefinedFunction(name.to_string()))?;
+
+        self.state.reset();
+        self.registers.clear();
+        for (i, value) in args.into_iter().enumerate() {
+            if i < 16 {
+                self.registers.set(i as u8, value);
+            }
+        }
+        self.state.pc = func_offset;
+
+        let mut last_value = None;
+
+        while self.state.is_running() && self.state.pc < self.instructions.len() {
+            let result = self.step()?;
+            if let Some(value) = result {
+                last_value = Some(value);
+            }
+        }
+
+        Ok(last_value)
+    }
+
     /// Execute a single instruction
     pub fn step(&mut self) -> Result<Option<Value>> {
         if self.state.pc >= self.instructions.len() {
@@ -82,15 +613,212 @@ impl VM {
             return Ok(None);
         }
 
-        let inst = self.instructions[self.state.pc].clone();
+        // A loop header that already has a finalized trace runs the
+        // trace directly instead of re-dispatching its body.
+        if self.recording.is_none() && self.traces.contains_key(&self.state.pc) {
+            return self.run_trace(self.state.pc);
+        }
+
+        if self.debug_mode && self.debugger.is_some() && self.should_break_at(self.state.pc) {
+            self.run_debug_loop()?;
+        }
+
+        let pc = self.state.pc;
+        let inst = self.instructions[pc].clone();
         self.state.pc += 1;
         self.instruction_count += 1;
 
         if self.debug_mode {
-            println!("PC: {}, Instruction: {:?}", self.state.pc - 1, inst);
+            println!("PC: {}, Instruction: {:?}", pc, inst);
+        }
+
+        let exec_result = self.execute_instruction(inst.clone());
+        if exec_result.is_err() {
+            // Resolve source locations/breakpoints against the
+            // instruction that actually raised, not the one `pc += 1`
+            // above already advanced past.
+            self.state.pc = pc;
         }
+        let result = exec_result?;
+
+        // The back-edge that starts a recording is not part of the loop
+        // body, so only steps taken while already recording are recorded.
+        let was_recording = self.recording.is_some();
+
+        // A jump that lands at or before its own origin is a backward
+        // branch, i.e. a loop header. Count how often each header is
+        // reached and start recording once one goes hot.
+        if self.state.pc <= pc && branch_target(pc, &inst).is_some() {
+            let header_pc = self.state.pc;
+            let count = self.hot_loop_counts.entry(header_pc).or_insert(0);
+            *count += 1;
+
+            if *count == HOT_LOOP_THRESHOLD
+                && self.recording.is_none()
+                && !self.traces.contains_key(&header_pc)
+            {
+                self.recording = Some(TraceRecorder::new(header_pc));
+            }
+        }
+
+        if was_recording {
+            let guard = self.guard_for(&inst);
+            let recorder = self.recording.as_mut().expect("checked above");
+            recorder.record(pc, inst, guard);
 
-        self.execute_instruction(inst)
+            let header_pc = recorder.header_pc;
+            if self.state.pc == header_pc || recorder.is_too_long() {
+                let recorder = self.recording.take().expect("checked above");
+                self.traces.insert(header_pc, recorder.finalize());
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Replay a finalized trace for the loop header at `header_pc`.
+    ///
+    /// Every guard is re-checked against live state; the first one that
+    /// fails deoptimizes back to `execute_instruction`, resuming from
+    /// the exact PC the trace was at when the guard was recorded.
+    fn run_trace(&mut self, header_pc: usize) -> Result<Option<Value>> {
+        let trace = self
+            .traces
+            .get(&header_pc)
+            .expect("caller checked trace exists")
+            .clone();
+
+        let mut last_value = None;
+
+        for step in trace.steps {
+            // A finalized trace replays every instruction in the loop
+            // body without going back through `step()`'s dispatch, so
+            // it has to re-check breakpoints/step-mode itself -- once a
+            // loop goes hot, breakpoints inside it would otherwise stop
+            // firing silently.
+            if self.debug_mode && self.debugger.is_some() && self.should_break_at(step.pc) {
+                self.state.pc = step.pc;
+                self.run_debug_loop()?;
+            }
+
+            if let Some(guard) = &step.guard {
+                if !guard.holds(self) {
+                    // Deoptimize: hand control back to `step()` at the
+                    // instruction the trace was at. `step()` owns the
+                    // pc-increment-then-dispatch protocol that jump
+                    // offsets are computed relative to, so we must not
+                    // dispatch it ourselves here.
+                    self.state.pc = step.pc;
+                    return Ok(last_value);
+                }
+            }
+
+            // A `CastR` whose type guard held is redundant: its source
+            // already has the target type, so it runs as a plain move.
+            let instruction = match (step.instruction, &step.guard) {
+                (Instruction::CastR { dst, src, .. }, Some(Guard::Type { .. })) => {
+                    Instruction::MoveR { dst, src }
+                }
+                (instruction, _) => instruction,
+            };
+
+            self.state.pc = step.pc + 1;
+            self.instruction_count += 1;
+            let exec_result = self.execute_instruction(instruction);
+            if exec_result.is_err() {
+                // Same off-by-one as `step()`: resolve against the
+                // instruction that raised, not the pc already advanced past.
+                self.state.pc = step.pc;
+            }
+            if let Some(value) = exec_result? {
+                last_value = Some(value);
+            }
+        }
+
+        Ok(last_value)
+    }
+
+    /// True if `pc` should stop execution and hand control to the
+    /// attached debugger.
+    fn should_break_at(&self, pc: usize) -> bool {
+        if self.breakpoints.contains(&pc) {
+            return true;
+        }
+
+        match self.step_mode {
+            Some(StepMode::Instruction) => true,
+            Some(StepMode::Over { depth }) => self.state.call_stack.len() <= depth,
+            Some(StepMode::Out { depth }) => self.state.call_stack.len() < depth,
+            None => false,
+        }
+    }
+
+    /// Hand control to the attached debugger until it picks an action
+    /// that resumes execution (or quits).
+    fn run_debug_loop(&mut self) -> Result<()> {
+        loop {
+            let trace = self.build_stack_trace();
+            let pc = self.state.pc;
+            let action = self
+                .debugger
+                .as_mut()
+                .expect("caller checked debugger is attached")
+                .next_action(pc, &trace);
+
+            match action {
+                DebugAction::Continue => {
+                    self.step_mode = None;
+                    return Ok(());
+                }
+                DebugAction::StepInstruction => {
+                    self.step_mode = Some(StepMode::Instruction);
+                    return Ok(());
+                }
+                DebugAction::StepOver => {
+                    self.step_mode = Some(StepMode::Over { depth: self.state.call_stack.len() });
+                    return Ok(());
+                }
+                DebugAction::StepOut => {
+                    self.step_mode = Some(StepMode::Out { depth: self.state.call_stack.len() });
+                    return Ok(());
+                }
+                DebugAction::SetBreakpoint(bp) => {
+                    self.breakpoints.insert(bp);
+                }
+                DebugAction::ClearBreakpoint(bp) => {
+                    self.breakpoints.remove(&bp);
+                }
+                DebugAction::ReadLocal(name) => {
+                    let value = self.state.globals.get(&name).cloned().unwrap_or(Value::Empty);
+                    println!("{} = {:?}", name, value);
+                }
+                DebugAction::WriteLocal(name, value) => {
+                    self.state.globals.insert(name, value);
+                }
+                DebugAction::Quit => {
+                    self.debugger = None;
+                    self.step_mode = None;
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    /// Derive the guard (if any) an instruction needs on trace replay.
+    fn guard_for(&self, inst: &Instruction) -> Option<Guard> {
+        match inst {
+            Instruction::JumpIfR { cond, .. } | Instruction::JumpIfNotR { cond, .. } => {
+                Some(Guard::Branch {
+                    cond: *cond,
+                    truthy: self.registers.get(*cond).is_truthy(),
+                })
+            }
+            Instruction::CastR { src, to_type, .. } => Some(Guard::Type {
+                reg: *src,
+                expected: self.get_type_from_id(*to_type),
+            }),
+            _ => None,
+        }
     }
 
     /// Execute an instruction
@@ -583,8 +1311,11 @@ impl VM {
 
             Instruction::BreakPoint => {
                 if self.debug_mode {
-                    println!("BREAKPOINT at PC: {}", self.state.pc - 1);
-                    // TODO: Implement debugger
+                    if self.debugger.is_some() {
+                        self.run_debug_loop()?;
+                    } else {
+                        println!("BREAKPOINT at PC: {}", self.state.pc - 1);
+                    }
                 }
             }
 
@@ -646,7 +1377,7 @@ impl VM {
         trace.push(StackFrame {
             function: "main".to_string(),
             pc: self.state.pc,
-            source_location: None,
+            source_location: self.source_map.resolve(self.state.pc),
         });
 
         // Add call frames
@@ -658,12 +1389,24 @@ impl VM {
             trace.push(StackFrame {
                 function: func_name,
                 pc: frame.return_address,
-                source_location: None,
+                source_location: self.source_map.resolve(frame.return_address),
             });
         }
 
         trace
     }
+
+    /// Render a runtime error prefixed with the source location of the
+    /// point it occurred at, when one is known, instead of a raw PC.
+    pub fn describe_error(&self, err: &RuntimeError) -> String {
+        match self.source_map.resolve(self.state.pc) {
+            Some(location) => format!(
+                "{}:{}:{}: {}",
+                location.file, location.line, location.column, err
+            ),
+            None => err.to_string(),
+        }
+    }
 }
 
 impl Default for VM {
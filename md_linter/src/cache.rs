@@ -0,0 +1,88 @@
+//! Incremental lint cache
+//!
+//! Caches the `Violation`s produced for a file, keyed by a hash of the
+//! file's contents and the active rule configuration, so that repeated
+//! `check` runs over large docs trees can skip files that have not
+//! changed since the last run.
+
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::config::MarkdownLintConfig;
+use crate::rules::Violation;
+
+/// Default location for on-disk cache entries.
+pub const DEFAULT_CACHE_DIR: &str = ".md_linter_cache";
+
+/// On-disk cache of lint results, keyed by content + config hash.
+pub struct LintCache {
+    dir: PathBuf,
+    config_hash: u64,
+}
+
+impl LintCache {
+    /// Open (creating if needed) the cache directory for a given rule
+    /// configuration. Entries written under a different `config_hash`
+    /// are invalidated automatically, since they live under a different
+    /// key and are simply never looked up again.
+    pub fn open(dir: &Path, config_hash: u64) -> Result<Self> {
+        fs::create_dir_all(dir)?;
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            config_hash,
+        })
+    }
+
+    fn key(&self, content: &str) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        content.hash(&mut hasher);
+        self.config_hash.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn entry_path(&self, content: &str) -> PathBuf {
+        self.dir.join(format!("{:016x}.json", self.key(content)))
+    }
+
+    /// Look up a cached result for `content`, if any.
+    pub fn get(&self, content: &str) -> Option<Vec<Violation>> {
+        let data = fs::read(self.entry_path(content)).ok()?;
+        serde_json::from_slice(&data).ok()
+    }
+
+    /// Store a result for `content`.
+    ///
+    /// Callers must never call this for a file that produced a
+    /// read/parse error, mirroring the "don't cache parse errors"
+    /// invariant: a transient error should not persist as an empty
+    /// cached result on the next run.
+    pub fn put(&self, content: &str, violations: &[Violation]) -> Result<()> {
+        let data = serde_json::to_vec(violations)?;
+        fs::write(self.entry_path(content), data)?;
+        Ok(())
+    }
+}
+
+/// Hash a rule configuration so cache entries invalidate whenever the
+/// configuration that produced them changes.
+pub fn hash_config(config: &MarkdownLintConfig) -> Result<u64> {
+    // `rules` is a `HashMap`, whose iteration order differs from one
+    // process to the next; hash its entries in key order so an unchanged
+    // configuration hashes the same on every run.
+    let mut rules: Vec<_> = config.rules.iter().collect();
+    rules.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut hasher = DefaultHasher::new();
+    config.default.hash(&mut hasher);
+    config.ignore.hash(&mut hasher);
+    config.include.hash(&mut hasher);
+    for (id, value) in rules {
+        id.hash(&mut hasher);
+        serde_yaml::to_string(value)?.hash(&mut hasher);
+    }
+    Ok(hasher.finish())
+}
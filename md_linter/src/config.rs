@@ -4,34 +4,35 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 
-use crate::rules::md013::Md013Config;
-
 #[derive(Debug, Deserialize, Serialize)]
 pub struct MarkdownLintConfig {
     #[serde(default = "default_true")]
     pub default: bool,
 
-    #[serde(rename = "MD013", default)]
-    pub md013: Option<Md013ConfigWrapper>,
-
-    #[serde(rename = "MD041", default)]
-    pub md041: Option<bool>,
-
+    /// Glob patterns excluded from directory discovery, in addition to
+    /// `.gitignore`.
+    #[serde(default)]
+    pub ignore: Vec<String>,
+
+    /// Glob patterns that restrict directory discovery; when non-empty,
+    /// only matching files are linted.
+    #[serde(default)]
+    pub include: Vec<String>,
+
+    /// Every other top-level key, keyed by rule id (e.g. `MD013`,
+    /// `MD041`): `false` disables the rule, `true` enables it with
+    /// defaults, and a mapping enables it with that rule-specific
+    /// configuration. Read generically by `RuleRegistry::from_config`
+    /// against whichever rules are registered there, so adding a new
+    /// lint rule needs no change to this struct.
     #[serde(flatten)]
-    pub other_rules: HashMap<String, serde_yaml::Value>,
+    pub rules: HashMap<String, serde_yaml::Value>,
 }
 
 fn default_true() -> bool {
     true
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-#[serde(untagged)]
-pub enum Md013ConfigWrapper {
-    Enabled(bool),
-    Config(Md013Config),
-}
-
 impl MarkdownLintConfig {
     pub fn from_file(path: &Path) -> Result<Self> {
         let content = fs::read_to_string(path)
@@ -42,21 +43,6 @@ impl MarkdownLintConfig {
 
         Ok(config)
     }
-
-    pub fn get_md013_config(&self) -> Option<Md013Config> {
-        match &self.md013 {
-            Some(Md013ConfigWrapper::Enabled(false)) => None,
-            Some(Md013ConfigWrapper::Enabled(true)) => Some(Md013Config::default()),
-            Some(Md013ConfigWrapper::Config(config)) => Some(config.clone()),
-            None => {
-                if self.default {
-                    Some(Md013Config::default())
-                } else {
-                    None
-                }
-            }
-        }
-    }
 }
 
 #[cfg(test)]
@@ -84,10 +70,23 @@ MD041: false
         let config = MarkdownLintConfig::from_file(file.path()).unwrap();
 
         assert!(config.default);
-        let md013_config = config.get_md013_config().unwrap();
-        assert_eq!(md013_config.line_length, 100);
-        assert!(!md013_config.code_blocks);
-        assert!(!md013_config.tables);
+        assert_eq!(
+            config.rules.get("MD013").unwrap().get("line_length").unwrap(),
+            &serde_yaml::Value::from(100)
+        );
+        assert_eq!(config.rules.get("MD041").unwrap(), &serde_yaml::Value::from(false));
+    }
+
+    #[test]
+    fn test_default_flag_parses_independently_of_rules() {
+        let yaml = "default: false\n";
+
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "{}", yaml).unwrap();
+
+        let config = MarkdownLintConfig::from_file(file.path()).unwrap();
+        assert!(!config.default);
+        assert!(config.rules.is_empty());
     }
 
     #[test]
@@ -101,6 +100,6 @@ MD013: false
         write!(file, "{}", yaml).unwrap();
 
         let config = MarkdownLintConfig::from_file(file.path()).unwrap();
-        assert!(config.get_md013_config().is_none());
+        assert_eq!(config.rules.get("MD013").unwrap(), &serde_yaml::Value::from(false));
     }
 }
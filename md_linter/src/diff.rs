@@ -0,0 +1,152 @@
+//! Unified diff rendering
+//!
+//! Computes a line-level diff between two pieces of text using the
+//! longest common subsequence and renders it in the standard unified
+//! diff format (`@@ -a,b +c,d @@` hunks with up to 3 lines of context),
+//! the same format `diff -u`/`rustfmt --check` use.
+
+const CONTEXT: usize = 3;
+
+/// One line of an edit script: unchanged, removed from the original, or
+/// added in the new version.
+#[derive(Debug, Clone, PartialEq)]
+enum DiffLine<'a> {
+    Context(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// Compute the LCS-based edit script between two line slices.
+fn diff_lines<'a>(original: &[&'a str], fixed: &[&'a str]) -> Vec<DiffLine<'a>> {
+    let n = original.len();
+    let m = fixed.len();
+
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if original[i] == fixed[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut script = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if original[i] == fixed[j] {
+            script.push(DiffLine::Context(original[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            script.push(DiffLine::Removed(original[i]));
+            i += 1;
+        } else {
+            script.push(DiffLine::Added(fixed[j]));
+            j += 1;
+        }
+    }
+    script.extend(original[i..n].iter().map(|line| DiffLine::Removed(line)));
+    script.extend(fixed[j..m].iter().map(|line| DiffLine::Added(line)));
+
+    script
+}
+
+/// Render a unified diff between `original` and `fixed`. Returns an
+/// empty string when the two are identical.
+pub fn unified_diff(original: &str, fixed: &str) -> String {
+    let original_lines: Vec<&str> = original.lines().collect();
+    let fixed_lines: Vec<&str> = fixed.lines().collect();
+    let script = diff_lines(&original_lines, &fixed_lines);
+
+    let changed: Vec<usize> = script
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, DiffLine::Context(_)))
+        .map(|(idx, _)| idx)
+        .collect();
+
+    if changed.is_empty() {
+        return String::new();
+    }
+
+    // Group changed lines into hunks, coalescing hunks whose surrounding
+    // context windows overlap so they render as one `@@` block.
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for idx in changed {
+        let start = idx.saturating_sub(CONTEXT);
+        let end = (idx + CONTEXT + 1).min(script.len());
+        match hunks.last_mut() {
+            Some((_, last_end)) if start <= *last_end => *last_end = end.max(*last_end),
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    hunks
+        .into_iter()
+        .map(|(start, end)| render_hunk(&script, start, end))
+        .collect()
+}
+
+fn render_hunk(script: &[DiffLine], start: usize, end: usize) -> String {
+    let mut orig_line = 1usize;
+    let mut fixed_line = 1usize;
+    for line in &script[..start] {
+        match line {
+            DiffLine::Context(_) => {
+                orig_line += 1;
+                fixed_line += 1;
+            }
+            DiffLine::Removed(_) => orig_line += 1,
+            DiffLine::Added(_) => fixed_line += 1,
+        }
+    }
+
+    let orig_start = orig_line;
+    let fixed_start = fixed_line;
+    let mut orig_count = 0;
+    let mut fixed_count = 0;
+    let mut body = String::new();
+
+    for line in &script[start..end] {
+        match line {
+            DiffLine::Context(text) => {
+                body.push_str(&format!(" {}\n", text));
+                orig_count += 1;
+                fixed_count += 1;
+            }
+            DiffLine::Removed(text) => {
+                body.push_str(&format!("-{}\n", text));
+                orig_count += 1;
+            }
+            DiffLine::Added(text) => {
+                body.push_str(&format!("+{}\n", text));
+                fixed_count += 1;
+            }
+        }
+    }
+
+    format!(
+        "@@ -{},{} +{},{} @@\n{}",
+        orig_start, orig_count, fixed_start, fixed_count, body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identical_content_has_no_diff() {
+        assert_eq!(unified_diff("a\nb\nc", "a\nb\nc"), "");
+    }
+
+    #[test]
+    fn test_single_line_change() {
+        let diff = unified_diff("a\nb\nc", "a\nx\nc");
+        assert!(diff.contains("@@ -1,3 +1,3 @@"));
+        assert!(diff.contains("-b"));
+        assert!(diff.contains("+x"));
+    }
+}
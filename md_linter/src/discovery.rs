@@ -0,0 +1,89 @@
+//! Recursive, gitignore-aware file discovery
+//!
+//! Expands file, directory, and glob arguments from the command line
+//! into the concrete list of Markdown files to lint, honoring
+//! `.gitignore` and the `ignore`/`include` glob lists in
+//! `.markdownlint.yaml`.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::WalkBuilder;
+
+use crate::config::MarkdownLintConfig;
+
+/// Extensions treated as Markdown files during directory discovery.
+const MARKDOWN_EXTENSIONS: &[&str] = &["md", "markdown"];
+
+fn build_glob_set(patterns: &[String]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(Glob::new(pattern)?);
+    }
+    Ok(builder.build()?)
+}
+
+fn is_markdown_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| MARKDOWN_EXTENSIONS.contains(&ext))
+        .unwrap_or(false)
+}
+
+fn is_excluded(path: &Path, ignore_globs: &GlobSet, include_globs: &GlobSet) -> bool {
+    if !include_globs.is_empty() && !include_globs.is_match(path) {
+        return true;
+    }
+    ignore_globs.is_match(path)
+}
+
+/// Expand `inputs` (files, directories, glob patterns, or the stdin
+/// marker `-`) into the list of Markdown files to lint.
+pub fn discover_files(
+    inputs: &[PathBuf],
+    config: &MarkdownLintConfig,
+    respect_gitignore: bool,
+) -> Result<Vec<PathBuf>> {
+    let ignore_globs = build_glob_set(&config.ignore)?;
+    let include_globs = build_glob_set(&config.include)?;
+
+    let mut files = Vec::new();
+
+    for input in inputs {
+        if input.as_os_str() == "-" {
+            files.push(input.clone());
+            continue;
+        }
+
+        if input.is_dir() {
+            let mut walker = WalkBuilder::new(input);
+            walker.git_ignore(respect_gitignore).git_exclude(respect_gitignore);
+
+            for entry in walker.build() {
+                let path = entry?.into_path();
+                if path.is_file()
+                    && is_markdown_file(&path)
+                    && !is_excluded(&path, &ignore_globs, &include_globs)
+                {
+                    files.push(path);
+                }
+            }
+            continue;
+        }
+
+        let pattern = input.to_string_lossy();
+        if pattern.contains(['*', '?', '[']) {
+            for entry in glob::glob(&pattern)? {
+                let path = entry?;
+                if path.is_file() && !is_excluded(&path, &ignore_globs, &include_globs) {
+                    files.push(path);
+                }
+            }
+        } else {
+            files.push(input.clone());
+        }
+    }
+
+    Ok(files)
+}
@@ -1,12 +1,54 @@
+mod cache;
 mod config;
+mod diff;
+mod discovery;
+mod reporter;
 mod rules;
 
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
+use rayon::prelude::*;
+use std::io::Read;
 use std::path::{Path, PathBuf};
 
+use crate::cache::LintCache;
 use crate::config::MarkdownLintConfig;
-use crate::rules::md013::Md013Linter;
+use crate::reporter::OutputFormat;
+use crate::rules::{RuleRegistry, Violation};
+
+/// File path that means "read from stdin" instead of a real file.
+const STDIN_MARKER: &str = "-";
+/// Synthetic display name used for violations found in stdin input.
+const STDIN_DISPLAY: &str = "<stdin>";
+
+fn is_stdin(path: &Path) -> bool {
+    path == Path::new(STDIN_MARKER)
+}
+
+/// Resolve a file argument to its content, reading stdin when `file_path`
+/// is `-`. Returns the path to display in reports alongside the content.
+fn read_input(file_path: &Path) -> Result<(PathBuf, String)> {
+    if is_stdin(file_path) {
+        let mut content = String::new();
+        std::io::stdin()
+            .read_to_string(&mut content)
+            .context("Failed to read from stdin")?;
+        Ok((PathBuf::from(STDIN_DISPLAY), content))
+    } else {
+        let content = std::fs::read_to_string(file_path)
+            .with_context(|| format!("Failed to read file: {}", file_path.display()))?;
+        Ok((file_path.to_path_buf(), content))
+    }
+}
+
+/// `files` as given on the command line, defaulting to stdin when empty.
+fn files_or_stdin(files: &[PathBuf]) -> Vec<PathBuf> {
+    if files.is_empty() {
+        vec![PathBuf::from(STDIN_MARKER)]
+    } else {
+        files.to_vec()
+    }
+}
 
 #[derive(Parser)]
 #[command(name = "md_linter")]
@@ -27,6 +69,14 @@ enum Commands {
         /// Path to the configuration file (default: .markdownlint.yaml)
         #[arg(short, long, default_value = ".markdownlint.yaml")]
         config: PathBuf,
+
+        /// Output format for violations
+        #[arg(long, value_enum, default_value = "human")]
+        output_format: OutputFormat,
+
+        /// Don't honor .gitignore when expanding directories
+        #[arg(long)]
+        no_ignore: bool,
     },
 
     /// Fix markdown files
@@ -42,6 +92,20 @@ enum Commands {
         /// Dry run - show what would be fixed without writing changes
         #[arg(short, long)]
         dry_run: bool,
+
+        /// Print a unified diff instead of writing changes; exits nonzero
+        /// if any file would change
+        #[arg(long)]
+        diff: bool,
+
+        /// Re-check fixed output and fail if any fixable violation
+        /// didn't converge, instead of writing an incompletely fixed file
+        #[arg(long)]
+        verify: bool,
+
+        /// Don't honor .gitignore when expanding directories
+        #[arg(long)]
+        no_ignore: bool,
     },
 }
 
@@ -49,34 +113,56 @@ fn main() -> Result<()> {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Check { files, config } => {
-            check_files(&files, &config)?;
+        Commands::Check { files, config, output_format, no_ignore } => {
+            check_files(&files, &config, output_format, no_ignore)?;
         }
-        Commands::Fix { files, config, dry_run } => {
-            fix_files(&files, &config, dry_run)?;
+        Commands::Fix { files, config, dry_run, diff, verify, no_ignore } => {
+            fix_files(&files, &config, dry_run, diff, verify, no_ignore)?;
         }
     }
 
     Ok(())
 }
 
-fn check_files(files: &[PathBuf], config_path: &Path) -> Result<()> {
+fn check_files(
+    files: &[PathBuf],
+    config_path: &Path,
+    output_format: OutputFormat,
+    no_ignore: bool,
+) -> Result<()> {
     let config = MarkdownLintConfig::from_file(config_path)
         .with_context(|| format!("Failed to load config from: {}", config_path.display()))?;
+    let registry = RuleRegistry::from_config(&config)?;
+    let config_hash = cache::hash_config(&config)?;
+    let cache = LintCache::open(Path::new(cache::DEFAULT_CACHE_DIR), config_hash)?;
+    let reporter = output_format.reporter();
+
+    let discovered = discovery::discover_files(&files_or_stdin(files), &config, !no_ignore)?;
+    let inputs: Vec<(PathBuf, String)> = discovered
+        .iter()
+        .map(|file_path| read_input(file_path))
+        .collect::<Result<_>>()?;
+
+    let results: Vec<Result<(&PathBuf, Vec<Violation>)>> = inputs
+        .par_iter()
+        .map(|(display_path, content)| {
+            if let Some(violations) = cache.get(content) {
+                return Ok((display_path, violations));
+            }
 
-    let mut found_violations = false;
-
-    if let Some(md013_config) = config.get_md013_config() {
-        let linter = Md013Linter::new(md013_config);
+            let violations = registry.check(content);
+            cache.put(content, &violations)?;
+            Ok((display_path, violations))
+        })
+        .collect();
 
-        for file_path in files {
-            let violations = linter.check_file(file_path)
-                .with_context(|| format!("Failed to check file: {}", file_path.display()))?;
+    let mut found_violations = false;
 
-            if !violations.is_empty() {
-                found_violations = true;
-                print!("{}", linter.format_violations(&violations, file_path));
-            }
+    for result in results {
+        let (file_path, violations) = result?;
+        if !violations.is_empty() {
+            found_violations = true;
+            print!("{}", reporter.report(&violations, file_path));
         }
     }
 
@@ -87,28 +173,60 @@ fn check_files(files: &[PathBuf], config_path: &Path) -> Result<()> {
     Ok(())
 }
 
-fn fix_files(files: &[PathBuf], config_path: &Path, dry_run: bool) -> Result<()> {
+fn fix_files(
+    files: &[PathBuf],
+    config_path: &Path,
+    dry_run: bool,
+    show_diff: bool,
+    verify: bool,
+    no_ignore: bool,
+) -> Result<()> {
     let config = MarkdownLintConfig::from_file(config_path)
         .with_context(|| format!("Failed to load config from: {}", config_path.display()))?;
+    let registry = RuleRegistry::from_config(&config)?;
+
+    let mut any_diff = false;
 
-    if let Some(md013_config) = config.get_md013_config() {
-        let linter = Md013Linter::new(md013_config);
-
-        for file_path in files {
-            let fixed_content = linter.fix_file(file_path)
-                .with_context(|| format!("Failed to fix file: {}", file_path.display()))?;
-
-            if dry_run {
-                println!("=== {} (preview) ===", file_path.display());
-                println!("{}", fixed_content);
-                println!();
-            } else {
-                std::fs::write(file_path, &fixed_content)
-                    .with_context(|| format!("Failed to write fixed content to: {}", file_path.display()))?;
-                println!("Fixed: {}", file_path.display());
+    let discovered = discovery::discover_files(&files_or_stdin(files), &config, !no_ignore)?;
+    for file_path in &discovered {
+        let (display_path, content) = read_input(file_path)?;
+
+        if show_diff {
+            let (_, hunks) = registry.fix_with_diff(&content);
+            if !hunks.is_empty() {
+                any_diff = true;
+                println!("--- {}", display_path.display());
+                println!("+++ {}", display_path.display());
+                print!("{}", hunks);
             }
+            continue;
+        }
+
+        let fixed_content = if verify {
+            registry
+                .fix_verified(&content)
+                .with_context(|| format!("Fix did not converge for: {}", display_path.display()))?
+        } else {
+            registry.fix(&content)
+        };
+
+        if is_stdin(file_path) {
+            // Act as a formatting filter: the fixed content is the output.
+            print!("{}", fixed_content);
+        } else if dry_run {
+            println!("=== {} (preview) ===", display_path.display());
+            println!("{}", fixed_content);
+            println!();
+        } else {
+            std::fs::write(file_path, &fixed_content)
+                .with_context(|| format!("Failed to write fixed content to: {}", file_path.display()))?;
+            println!("Fixed: {}", file_path.display());
         }
     }
 
+    if show_diff && any_diff {
+        std::process::exit(1);
+    }
+
     Ok(())
 }
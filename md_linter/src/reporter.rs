@@ -0,0 +1,197 @@
+//! Violation reporters
+//!
+//! Formats `Violation`s for different consumers: a human reading a
+//! terminal, an editor or CI system parsing machine-readable records,
+//! or GitHub Actions annotating a pull request diff. Every rule's
+//! output goes through the same `Reporter`, so adding a rule never
+//! requires adding a formatter.
+
+use std::path::Path;
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::rules::{format_violations, Violation};
+
+/// Supported output formats for `check`.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable text (default).
+    Human,
+    /// One JSON record per violation.
+    Json,
+    /// GitHub Actions `::error` workflow-command annotations.
+    Github,
+    /// `path:line:col: [RULE] message`, one per line.
+    Compact,
+}
+
+impl OutputFormat {
+    /// Build the reporter for this format.
+    pub fn reporter(self) -> Box<dyn Reporter> {
+        match self {
+            OutputFormat::Human => Box::new(HumanReporter),
+            OutputFormat::Json => Box::new(JsonReporter),
+            OutputFormat::Github => Box::new(GithubReporter),
+            OutputFormat::Compact => Box::new(CompactReporter),
+        }
+    }
+}
+
+/// Formats a file's violations into report text.
+pub trait Reporter {
+    fn report(&self, violations: &[Violation], file_path: &Path) -> String;
+}
+
+/// The original `path:line RULE message` text format.
+pub struct HumanReporter;
+
+impl Reporter for HumanReporter {
+    fn report(&self, violations: &[Violation], file_path: &Path) -> String {
+        format_violations(violations, file_path)
+    }
+}
+
+#[derive(Serialize)]
+struct JsonRecord<'a> {
+    file: String,
+    line: usize,
+    column: usize,
+    rule_id: &'a str,
+    message: &'a str,
+    fixable: bool,
+}
+
+/// One JSON object per violation, suitable for editor/CI consumption.
+pub struct JsonReporter;
+
+impl Reporter for JsonReporter {
+    fn report(&self, violations: &[Violation], file_path: &Path) -> String {
+        let mut output = String::new();
+
+        for violation in violations {
+            let record = JsonRecord {
+                file: file_path.display().to_string(),
+                line: violation.line_number,
+                column: violation.column,
+                rule_id: violation.rule_id,
+                message: &violation.message,
+                fixable: violation.fixable,
+            };
+
+            if let Ok(line) = serde_json::to_string(&record) {
+                output.push_str(&line);
+                output.push('\n');
+            }
+        }
+
+        output
+    }
+}
+
+/// GitHub Actions workflow-command annotations, so violations surface
+/// as inline comments on a pull request diff.
+pub struct GithubReporter;
+
+impl Reporter for GithubReporter {
+    fn report(&self, violations: &[Violation], file_path: &Path) -> String {
+        let mut output = String::new();
+
+        for violation in violations {
+            output.push_str(&format!(
+                "::error file={},line={},col={}::{}\n",
+                file_path.display(),
+                violation.line_number,
+                violation.column,
+                violation.message
+            ));
+        }
+
+        output
+    }
+}
+
+/// `path:line:col: [RULE] message`, one per line, for grep/editor parsing.
+pub struct CompactReporter;
+
+impl Reporter for CompactReporter {
+    fn report(&self, violations: &[Violation], file_path: &Path) -> String {
+        let mut output = String::new();
+
+        for violation in violations {
+            output.push_str(&format!(
+                "{}:{}:{}: [{}] {}\n",
+                file_path.display(),
+                violation.line_number,
+                violation.column,
+                violation.rule_id,
+                violation.message
+            ));
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_violation() -> Violation {
+        Violation {
+            rule_id: "MD013/line-length",
+            line_number: 12,
+            column: 81,
+            message: "Line length [Expected: 80; Actual: 95]".to_string(),
+            fixable: true,
+        }
+    }
+
+    #[test]
+    fn json_reporter_emits_one_record_per_line() {
+        let violations = vec![sample_violation()];
+        let output = JsonReporter.report(&violations, Path::new("doc.md"));
+
+        assert_eq!(output.lines().count(), 1);
+        let record: serde_json::Value = serde_json::from_str(output.trim_end()).unwrap();
+        assert_eq!(record["file"], "doc.md");
+        assert_eq!(record["line"], 12);
+        assert_eq!(record["column"], 81);
+        assert_eq!(record["rule_id"], "MD013/line-length");
+        assert_eq!(record["fixable"], true);
+    }
+
+    #[test]
+    fn json_reporter_emits_nothing_for_no_violations() {
+        assert_eq!(JsonReporter.report(&[], Path::new("doc.md")), "");
+    }
+
+    #[test]
+    fn github_reporter_formats_workflow_command_annotation() {
+        let violations = vec![sample_violation()];
+        let output = GithubReporter.report(&violations, Path::new("doc.md"));
+
+        assert_eq!(
+            output,
+            "::error file=doc.md,line=12,col=81::Line length [Expected: 80; Actual: 95]\n"
+        );
+    }
+
+    #[test]
+    fn compact_reporter_formats_path_line_col_rule_message() {
+        let violations = vec![sample_violation()];
+        let output = CompactReporter.report(&violations, Path::new("doc.md"));
+
+        assert_eq!(
+            output,
+            "doc.md:12:81: [MD013/line-length] Line length [Expected: 80; Actual: 95]\n"
+        );
+    }
+
+    #[test]
+    fn reporters_emit_nothing_for_no_violations() {
+        assert_eq!(GithubReporter.report(&[], Path::new("doc.md")), "");
+        assert_eq!(CompactReporter.report(&[], Path::new("doc.md")), "");
+        assert_eq!(HumanReporter.report(&[], Path::new("doc.md")), "");
+    }
+}
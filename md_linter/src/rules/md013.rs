@@ -5,6 +5,8 @@ use std::fs;
 use std::path::Path;
 use textwrap::{fill, Options};
 
+use crate::rules::{Rule, Violation};
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Md013Config {
     pub line_length: usize,
@@ -24,15 +26,6 @@ impl Default for Md013Config {
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct Violation {
-    pub line_number: usize,
-    pub actual_length: usize,
-    pub expected_length: usize,
-    #[allow(dead_code)]
-    pub line_content: String,
-}
-
 pub struct Md013Linter {
     config: Md013Config,
     code_block_regex: Regex,
@@ -78,10 +71,15 @@ impl Md013Linter {
             let line_length = line.chars().count();
             if line_length > self.config.line_length {
                 violations.push(Violation {
+                    rule_id: "MD013/line-length",
                     line_number: line_number + 1,
-                    actual_length: line_length,
-                    expected_length: self.config.line_length,
-                    line_content: line.to_string(),
+                    column: self.config.line_length + 1,
+                    message: format!(
+                        "Line length [Expected: {}; Actual: {}]",
+                        self.config.line_length, line_length
+                    ),
+                    // `fix_content` leaves headings unwrapped.
+                    fixable: !line.trim_start().starts_with('#'),
                 });
             }
         }
@@ -182,21 +180,25 @@ impl Md013Linter {
 
         result.join("\n")
     }
+}
 
-    pub fn format_violations(&self, violations: &[Violation], file_path: &Path) -> String {
-        let mut output = String::new();
-
-        for violation in violations {
-            output.push_str(&format!(
-                "{}:{} MD013/line-length Line length [Expected: {}; Actual: {}]\n",
-                file_path.display(),
-                violation.line_number,
-                violation.expected_length,
-                violation.actual_length
-            ));
-        }
+impl Rule for Md013Linter {
+    fn id(&self) -> &'static str {
+        "MD013"
+    }
+
+    fn check(&self, content: &str) -> Vec<Violation> {
+        self.check_content(content)
+    }
+
+    fn fix(&self, content: &str) -> String {
+        self.fix_content(content)
+    }
 
-        output
+    fn configure(&mut self, value: &serde_yaml::Value) -> Result<()> {
+        let config: Md013Config = serde_yaml::from_value(value.clone())?;
+        *self = Md013Linter::new(config);
+        Ok(())
     }
 }
 
@@ -236,6 +238,30 @@ mod tests {
         assert_eq!(violations.len(), 0);
     }
 
+    #[test]
+    fn test_configure_replaces_config() {
+        let mut linter = Md013Linter::new(Md013Config::default());
+        linter
+            .configure(&serde_yaml::Value::Mapping(
+                [
+                    (
+                        serde_yaml::Value::from("line_length"),
+                        serde_yaml::Value::from(20),
+                    ),
+                    (
+                        serde_yaml::Value::from("code_blocks"),
+                        serde_yaml::Value::from(false),
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+            ))
+            .unwrap();
+
+        let violations = linter.check_content("Short line\nThis is a very long line that exceeds twenty");
+        assert_eq!(violations.len(), 1);
+    }
+
     #[test]
     fn test_line_wrapping() {
         let config = Md013Config {
@@ -0,0 +1,62 @@
+//! MD041 - First line in file should be a top-level heading
+
+use crate::rules::{Rule, Violation};
+
+pub struct Md041Linter;
+
+impl Md041Linter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn check_content(&self, content: &str) -> Vec<Violation> {
+        match content.lines().next() {
+            Some(first_line) if first_line.trim_start().starts_with("# ") => Vec::new(),
+            Some(_) => vec![Violation {
+                rule_id: "MD041/first-line-heading",
+                line_number: 1,
+                column: 1,
+                message: "First line in file should be a top-level heading".to_string(),
+                fixable: false,
+            }],
+            None => Vec::new(),
+        }
+    }
+}
+
+impl Rule for Md041Linter {
+    fn id(&self) -> &'static str {
+        "MD041"
+    }
+
+    fn check(&self, content: &str) -> Vec<Violation> {
+        self.check_content(content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_top_level_heading_passes() {
+        let linter = Md041Linter::new();
+        let content = "# Title\n\nSome text.";
+        assert_eq!(linter.check_content(content).len(), 0);
+    }
+
+    #[test]
+    fn test_missing_heading_fails() {
+        let linter = Md041Linter::new();
+        let content = "Some text.\n\n# Title";
+        let violations = linter.check_content(content);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line_number, 1);
+    }
+
+    #[test]
+    fn test_empty_file_passes() {
+        let linter = Md041Linter::new();
+        assert_eq!(linter.check_content("").len(), 0);
+    }
+}
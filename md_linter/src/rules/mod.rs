@@ -0,0 +1,176 @@
+//! Lint rule registry
+//!
+//! Defines the `Rule` trait every lint rule implements and a
+//! `RuleRegistry` that instantiates the rules enabled in a
+//! `MarkdownLintConfig` and drives them uniformly, so `main.rs` no
+//! longer has to know about individual rule types.
+
+pub mod md013;
+pub mod md041;
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use md013::{Md013Config, Md013Linter};
+use md041::Md041Linter;
+use serde::{Deserialize, Serialize};
+
+use crate::config::MarkdownLintConfig;
+
+/// A single lint violation found while checking a file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Violation {
+    /// The rule and check that produced this violation, e.g.
+    /// `"MD013/line-length"`.
+    pub rule_id: &'static str,
+    /// 1-based line number the violation applies to.
+    pub line_number: usize,
+    /// 1-based column the violation applies to.
+    pub column: usize,
+    /// Human-readable description of the violation.
+    pub message: String,
+    /// Whether `Rule::fix` can resolve this violation automatically.
+    pub fixable: bool,
+}
+
+/// A lintable Markdown rule.
+///
+/// Implementations check content for violations and, optionally,
+/// rewrite content to fix them.
+pub trait Rule {
+    /// The rule id this implementation checks, e.g. `"MD013"`.
+    fn id(&self) -> &'static str;
+
+    /// Check `content` and return any violations found.
+    fn check(&self, content: &str) -> Vec<Violation>;
+
+    /// Fix `content`, returning the rewritten version.
+    ///
+    /// Rules that cannot auto-fix themselves can rely on this default,
+    /// which returns `content` unchanged.
+    fn fix(&self, content: &str) -> String {
+        content.to_string()
+    }
+
+    /// Apply this rule's configuration, given the YAML value found
+    /// under its id in the config file (any value other than a bare
+    /// `true`/`false`, which just toggles whether the rule is built at
+    /// all and never reaches here).
+    ///
+    /// Rules with no configuration besides enable/disable can rely on
+    /// this default no-op.
+    fn configure(&mut self, _value: &serde_yaml::Value) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Builds a fresh, default-configured instance of a registered rule.
+type RuleFactory = fn() -> Box<dyn Rule>;
+
+/// Every rule id this build knows how to construct, in the order they
+/// run. Adding a new lint rule means adding one entry here: its YAML
+/// key then becomes enable/disable- and configure-able from the config
+/// file with no other change to `RuleRegistry`.
+const RULE_FACTORIES: &[(&str, RuleFactory)] = &[
+    ("MD013", || Box::new(Md013Linter::new(Md013Config::default()))),
+    ("MD041", || Box::new(Md041Linter::new())),
+];
+
+/// Instantiates and drives the set of rules enabled by a config.
+pub struct RuleRegistry {
+    rules: Vec<Box<dyn Rule>>,
+}
+
+impl RuleRegistry {
+    /// Build a registry from the rules enabled in `config`, looking
+    /// each registered rule up by id in `config.rules` to decide
+    /// whether it runs and, for a mapping value, to configure it.
+    pub fn from_config(config: &MarkdownLintConfig) -> Result<Self> {
+        let mut rules: Vec<Box<dyn Rule>> = Vec::new();
+
+        for (id, new_rule) in RULE_FACTORIES {
+            let value = config.rules.get(*id);
+            let enabled = match value {
+                Some(serde_yaml::Value::Bool(enabled)) => *enabled,
+                Some(_) => true,
+                None => config.default,
+            };
+            if !enabled {
+                continue;
+            }
+
+            let mut rule = new_rule();
+            if let Some(value) = value.filter(|value| !value.is_bool()) {
+                rule.configure(value)
+                    .with_context(|| format!("invalid configuration for {id}"))?;
+            }
+            rules.push(rule);
+        }
+
+        Ok(Self { rules })
+    }
+
+    /// Check `content`, aggregating violations from every enabled rule.
+    pub fn check(&self, content: &str) -> Vec<Violation> {
+        self.rules.iter().flat_map(|rule| rule.check(content)).collect()
+    }
+
+    /// Fix `content` by running every enabled rule's fixer in sequence.
+    pub fn fix(&self, content: &str) -> String {
+        self.rules
+            .iter()
+            .fold(content.to_string(), |acc, rule| rule.fix(&acc))
+    }
+
+    /// True if no rules are enabled.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Fix `content`, paired with a unified diff of the change so
+    /// callers can preview or selectively apply it rather than
+    /// overwriting files blindly.
+    pub fn fix_with_diff(&self, content: &str) -> (String, String) {
+        let fixed = self.fix(content);
+        let diff = crate::diff::unified_diff(content, &fixed);
+        (fixed, diff)
+    }
+
+    /// Fix `content`, then re-check the result and error out listing
+    /// any line that still has a `fixable` violation — i.e. a rule's
+    /// fixer that didn't actually converge.
+    pub fn fix_verified(&self, content: &str) -> Result<String> {
+        let fixed = self.fix(content);
+        let remaining: Vec<Violation> =
+            self.check(&fixed).into_iter().filter(|v| v.fixable).collect();
+
+        if remaining.is_empty() {
+            Ok(fixed)
+        } else {
+            let lines = remaining
+                .iter()
+                .map(|v| v.line_number.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            anyhow::bail!("fix did not converge; fixable violations remain on line(s): {}", lines);
+        }
+    }
+}
+
+/// Format violations for human-readable terminal output, e.g.:
+/// `doc.md:12 MD013/line-length Line length [Expected: 80; Actual: 95]`
+pub fn format_violations(violations: &[Violation], file_path: &Path) -> String {
+    let mut output = String::new();
+
+    for violation in violations {
+        output.push_str(&format!(
+            "{}:{} {} {}\n",
+            file_path.display(),
+            violation.line_number,
+            violation.rule_id,
+            violation.message
+        ));
+    }
+
+    output
+}